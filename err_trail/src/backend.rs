@@ -0,0 +1,188 @@
+//! Routing between the backends. They are additive, e.g. through feature unification in a workspace. A set
+//! [Sink](crate::Sink) receives every event. Otherwise, with both `tracing` and `log`, an event goes to `tracing` if a
+//! subscriber is set, otherwise to `log`.
+
+use core::fmt::Display;
+use core::panic::Location;
+
+use crate::Level;
+
+/// Emits an event at a lowercase level, e.g. `error`, to the backend [backend] selects. The location of the event is
+/// the caller of the enclosing `#[track_caller]` method, unless given with `at location;`.
+macro_rules! route {
+    (at $location:expr; $level:ident, error = $error:expr) => {
+        route!(
+            @emit $location,
+            $level,
+            $error,
+            None,
+            route!(@tracing $location, $level, error = %$error),
+            route!(@log $location, $level, "{}", $error)
+        )
+    };
+    (at $location:expr; $level:ident, $message:expr, error = $error:expr) => {
+        route!(
+            @emit $location,
+            $level,
+            $message,
+            Some(&$error),
+            route!(@tracing $location, $level, error = %$error, "{}", $message),
+            route!(@log $location, $level, "{}: {}", $message, $error)
+        )
+    };
+    // The fields are emitted by the `tracing` or `log` event the closure creates
+    (at $location:expr; $level:ident, $message:expr, fields = $fields:expr, $error:expr) => {
+        route!(
+            @emit $location,
+            $level,
+            $message,
+            $error,
+            ($fields.0)(route!(@level $level), &$message, $error),
+            ($fields.0)(route!(@level $level), &$message, $error)
+        )
+    };
+    (at $location:expr; $level:ident, $message:expr) => {
+        route!(
+            @emit $location,
+            $level,
+            $message,
+            None,
+            route!(@tracing $location, $level, "{}", $message),
+            route!(@log $location, $level, "{}", $message)
+        )
+    };
+    (@emit $location:expr, $level:ident, $message:expr, $error:expr, $tracing:expr, $log:expr) => {
+        match crate::backend::backend() {
+            #[cfg(feature = "sink")]
+            crate::backend::Backend::Sink(sink) => sink.log(route!(@level $level), &$message, $error, $location),
+            #[cfg(feature = "tracing")]
+            crate::backend::Backend::Tracing => $tracing,
+            #[cfg(feature = "log")]
+            crate::backend::Backend::Log => $log,
+            crate::backend::Backend::None => {}
+        }
+    };
+    // The location is recorded as fields, since the file and line of a `tracing` event are those of its macro
+    (@tracing $location:expr, $level:ident, $($args:tt)*) => {
+        tracing::$level!(caller.file = $location.file(), caller.line = $location.line(), $($args)*)
+    };
+    (@log $location:expr, $level:ident, $($args:tt)*) => {
+        crate::backend::log(route!(@level $level), $location, format_args!($($args)*))
+    };
+    (@level error) => { crate::Level::Error };
+    (@level warn) => { crate::Level::Warn };
+    (@level info) => { crate::Level::Info };
+    (@level debug) => { crate::Level::Debug };
+    (@level trace) => { crate::Level::Trace };
+    ($($args:tt)*) => {
+        route!(at ::core::panic::Location::caller(); $($args)*)
+    };
+}
+
+/// The backend an event is routed to.
+pub(crate) enum Backend {
+    #[cfg(feature = "sink")]
+    Sink(&'static dyn crate::Sink),
+    #[cfg(feature = "tracing")]
+    Tracing,
+    #[cfg(feature = "log")]
+    Log,
+    None,
+}
+
+#[inline]
+pub(crate) fn backend() -> Backend {
+    #[cfg(feature = "sink")]
+    if let Some(sink) = crate::sink() {
+        return Backend::Sink(sink);
+    }
+    #[cfg(feature = "tracing")]
+    if __route_to_tracing() {
+        return Backend::Tracing;
+    }
+    #[cfg(feature = "log")]
+    if __route_to_log() {
+        return Backend::Log;
+    }
+    Backend::None
+}
+
+/// Logs a record with the call site of the context method as its file and line. The module of the call site cannot be
+/// captured by a method, so the target and module path stay those the records had when the `log` macros were called
+/// from `tracing_log_stub`.
+#[cfg(feature = "log")]
+#[inline]
+pub(crate) fn log(
+    level: crate::Level,
+    location: &'static Location<'static>,
+    args: core::fmt::Arguments,
+) {
+    let level = crate::__log_level(level);
+    if level <= log::STATIC_MAX_LEVEL && level <= log::max_level() {
+        log::logger().log(
+            &log::Record::builder()
+                .args(args)
+                .level(level)
+                .target("err_trail::tracing_log_stub")
+                .module_path_static(Some("err_trail::tracing_log_stub"))
+                .file_static(Some(location.file()))
+                .line(Some(location.line()))
+                .build(),
+        );
+    }
+}
+
+/// Emits an event at a runtime level, with a location captured earlier, e.g. by the adapters that log on poll.
+#[allow(unused_variables)]
+pub(crate) fn route_at(level: Level, message: &dyn Display, location: &'static Location<'static>) {
+    match level {
+        Level::Error => route!(at location; error, message),
+        Level::Warn => route!(at location; warn, message),
+        Level::Info => route!(at location; info, message),
+        Level::Debug => route!(at location; debug, message),
+        Level::Trace => route!(at location; trace, message),
+    }
+}
+
+/// Emits an event with an error at a runtime level, e.g. for the error returned from an `#[instrument_err]` function.
+#[cfg(all(
+    feature = "instrument",
+    any(feature = "tracing", feature = "log", feature = "sink")
+))]
+#[allow(unused_variables)]
+pub(crate) fn route_error_at(
+    level: Level,
+    message: &dyn Display,
+    error: &dyn Display,
+    location: &'static Location<'static>,
+) {
+    match level {
+        Level::Error => route!(at location; error, message, error = error),
+        Level::Warn => route!(at location; warn, message, error = error),
+        Level::Info => route!(at location; info, message, error = error),
+        Level::Debug => route!(at location; debug, message, error = error),
+        Level::Trace => route!(at location; trace, message, error = error),
+    }
+}
+
+/// If events are routed to `tracing`. With both the `tracing` and `log` features, this is the case when a `tracing`
+/// subscriber is set, globally or for the current scope.
+#[doc(hidden)]
+#[inline]
+pub fn __route_to_tracing() -> bool {
+    #[cfg(all(feature = "tracing", feature = "log"))]
+    return tracing::dispatcher::get_default(|dispatch| {
+        !dispatch.is::<tracing::subscriber::NoSubscriber>()
+    });
+    #[cfg(all(feature = "tracing", not(feature = "log")))]
+    return true;
+    #[cfg(not(feature = "tracing"))]
+    return false;
+}
+
+/// If events are routed to `log`.
+#[doc(hidden)]
+#[inline]
+pub fn __route_to_log() -> bool {
+    cfg!(feature = "log") && !__route_to_tracing()
+}
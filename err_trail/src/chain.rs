@@ -0,0 +1,77 @@
+use core::error::Error;
+use core::fmt::{self, Display};
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// How [DisplayChain] renders the sources of an error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChainStyle {
+    /// `top: cause: root`
+    Inline,
+    /// One error per line, with each source indented as `caused by: ..`
+    Multiline,
+}
+
+static CHAIN_STYLE: AtomicU8 = AtomicU8::new(ChainStyle::Inline as u8);
+
+/// Sets the [ChainStyle] used by [DisplayChain::new] and the `*_chain` methods. The default is [ChainStyle::Inline].
+pub fn set_chain_style(style: ChainStyle) {
+    CHAIN_STYLE.store(style as u8, Ordering::Relaxed);
+}
+
+/// The [ChainStyle] set with [set_chain_style].
+pub fn chain_style() -> ChainStyle {
+    match CHAIN_STYLE.load(Ordering::Relaxed) {
+        style if style == ChainStyle::Multiline as u8 => ChainStyle::Multiline,
+        _ => ChainStyle::Inline,
+    }
+}
+
+/// Displays an error followed by all of its [sources](Error::source). e.g.
+/// ```rust,ignore
+/// let value = result.with_warn_context(|err| format!("Could not load the cover: {}", DisplayChain::new(err)));
+/// ```
+pub struct DisplayChain<'a, E: ?Sized> {
+    error: &'a E,
+    style: ChainStyle,
+}
+
+impl<'a, E: Error + ?Sized> DisplayChain<'a, E> {
+    /// Uses the [ChainStyle] set with [set_chain_style].
+    pub fn new(error: &'a E) -> Self {
+        Self {
+            error,
+            style: chain_style(),
+        }
+    }
+
+    /// Renders the chain as `top: cause: root`.
+    pub fn inline(self) -> Self {
+        Self {
+            style: ChainStyle::Inline,
+            ..self
+        }
+    }
+
+    /// Renders one error per line, with each source indented.
+    pub fn multiline(self) -> Self {
+        Self {
+            style: ChainStyle::Multiline,
+            ..self
+        }
+    }
+}
+
+impl<E: Error + ?Sized> Display for DisplayChain<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        let mut source = self.error.source();
+        while let Some(error) = source {
+            match self.style {
+                ChainStyle::Inline => write!(f, ": {}", error)?,
+                ChainStyle::Multiline => write!(f, "\n  caused by: {}", error)?,
+            }
+            source = error.source();
+        }
+        Ok(())
+    }
+}
@@ -1,14 +1,47 @@
 #![allow(unused_variables)]
 
+use core::panic::Location;
+
 use defmt::Format;
 
+use crate::Level;
+
+/// Logs the message with the call site of the context method, since `defmt` records no location of its own. The
+/// location can also be given with `at location;`.
+macro_rules! emit {
+    (at $location:expr; $level:ident, $message:expr) => {{
+        let location: &Location = $location;
+        defmt::$level!("{} ({=str}:{=u32})", $message, location.file(), location.line())
+    }};
+    ($level:ident, $message:expr) => {
+        emit!(at Location::caller(); $level, $message)
+    };
+}
+
+/// Logs the message at a runtime level, with a location captured earlier, e.g. by the iterator adapters.
+fn emit_at(level: Level, message: &impl Format, location: &'static Location<'static>) {
+    match level {
+        Level::Error => emit!(at location; error, message),
+        Level::Warn => emit!(at location; warn, message),
+        Level::Info => emit!(at location; info, message),
+        Level::Debug => emit!(at location; debug, message),
+        Level::Trace => emit!(at location; trace, message),
+    }
+}
+
 mod sealed {
     /// A sealed trait to prevent external implementations.
     pub trait Sealed {}
 }
 
 /// For logging a [Result] when [Err] is encountered.
-#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(
+        feature = "defmt",
+        not(any(feature = "tracing", feature = "log", feature = "sink"))
+    )))
+)]
 pub trait ErrContextDefmt<T, E>: sealed::Sealed {
     /// If [Err], log context as "error".
     fn error_context(self, context: impl Format) -> Result<T, E>;
@@ -50,7 +83,13 @@ pub trait ErrContextDefmt<T, E>: sealed::Sealed {
 }
 
 /// For consuming a [Result]'s [Err] in [Format] when [Err] is encountered.
-#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(
+        feature = "defmt",
+        not(any(feature = "tracing", feature = "log", feature = "sink"))
+    )))
+)]
 pub trait ErrContextDisplayDefmt<T, E: Format>: sealed::Sealed {
     /// Consume [Err] of a [Result]. Log as "error".
     fn consume_as_error(self) -> Option<T>;
@@ -65,7 +104,13 @@ pub trait ErrContextDisplayDefmt<T, E: Format>: sealed::Sealed {
 }
 
 /// For logging an [Option] when [None] is encountered.
-#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(
+        feature = "defmt",
+        not(any(feature = "tracing", feature = "log", feature = "sink"))
+    )))
+)]
 pub trait NoneContextDefmt<T>: sealed::Sealed {
     /// If [None], log context as "error".
     fn error_context(self, context: impl Format) -> Option<T>;
@@ -95,135 +140,150 @@ pub trait NoneContextDefmt<T>: sealed::Sealed {
 impl<T, E> sealed::Sealed for Result<T, E> {}
 impl<T, E> ErrContextDefmt<T, E> for Result<T, E> {
     #[inline]
+    #[track_caller]
     fn error_context(self, context: impl Format) -> Result<T, E> {
         if self.is_err() {
-            defmt::error!("{}", context);
+            emit!(error, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn warn_context(self, context: impl Format) -> Result<T, E> {
         if self.is_err() {
-            defmt::warn!("{}", context);
+            emit!(warn, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn info_context(self, context: impl Format) -> Result<T, E> {
         if self.is_err() {
-            defmt::info!("{}", context);
+            emit!(info, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn debug_context(self, context: impl Format) -> Result<T, E> {
         if self.is_err() {
-            defmt::debug!("{}", context);
+            emit!(debug, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn trace_context(self, context: impl Format) -> Result<T, E> {
         if self.is_err() {
-            defmt::trace!("{}", context);
+            emit!(trace, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_error_context<F: FnOnce(&E) -> D, D: Format>(self, f: F) -> Result<T, E> {
         if let Err(err) = &self {
-            defmt::error!("{}", f(err));
+            emit!(error, f(err));
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_warn_context<F: FnOnce(&E) -> D, D: Format>(self, f: F) -> Result<T, E> {
         if let Err(err) = &self {
-            defmt::warn!("{}", f(err));
+            emit!(warn, f(err));
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_info_context<F: FnOnce(&E) -> D, D: Format>(self, f: F) -> Result<T, E> {
         if let Err(err) = &self {
-            defmt::info!("{}", f(err));
+            emit!(info, f(err));
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_debug_context<F: FnOnce(&E) -> D, D: Format>(self, f: F) -> Result<T, E> {
         if let Err(err) = &self {
-            defmt::debug!("{}", f(err));
+            emit!(debug, f(err));
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_trace_context<F: FnOnce(&E) -> D, D: Format>(self, f: F) -> Result<T, E> {
         if let Err(err) = &self {
-            defmt::trace!("{}", f(err));
+            emit!(trace, f(err));
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn consume_with_error<F: FnOnce(&E) -> D, D: Format>(self, f: F) -> Option<T> {
         match self {
             Ok(value) => Some(value),
             Err(err) => {
-                defmt::error!("{}", f(&err));
+                emit!(error, f(&err));
                 None
             }
         }
     }
 
     #[inline]
+    #[track_caller]
     fn consume_with_warn<F: FnOnce(&E) -> D, D: Format>(self, f: F) -> Option<T> {
         match self {
             Ok(value) => Some(value),
             Err(err) => {
-                defmt::warn!("{}", f(&err));
+                emit!(warn, f(&err));
                 None
             }
         }
     }
 
     #[inline]
+    #[track_caller]
     fn consume_with_info<F: FnOnce(&E) -> D, D: Format>(self, f: F) -> Option<T> {
         match self {
             Ok(value) => Some(value),
             Err(err) => {
-                defmt::info!("{}", f(&err));
+                emit!(info, f(&err));
                 None
             }
         }
     }
 
     #[inline]
+    #[track_caller]
     fn consume_with_debug<F: FnOnce(&E) -> D, D: Format>(self, f: F) -> Option<T> {
         match self {
             Ok(value) => Some(value),
             Err(err) => {
-                defmt::debug!("{}", f(&err));
+                emit!(debug, f(&err));
                 None
             }
         }
     }
 
     #[inline]
+    #[track_caller]
     fn consume_with_trace<F: FnOnce(&E) -> D, D: Format>(self, f: F) -> Option<T> {
         match self {
             Ok(value) => Some(value),
             Err(err) => {
-                defmt::trace!("{}", f(&err));
+                emit!(trace, f(&err));
                 None
             }
         }
@@ -232,55 +292,60 @@ impl<T, E> ErrContextDefmt<T, E> for Result<T, E> {
 
 impl<T, E: Format> ErrContextDisplayDefmt<T, E> for Result<T, E> {
     #[inline]
+    #[track_caller]
     fn consume_as_error(self) -> Option<T> {
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                defmt::error!("{}", err);
+                emit!(error, err);
                 None
             }
         }
     }
 
     #[inline]
+    #[track_caller]
     fn consume_as_warn(self) -> Option<T> {
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                defmt::warn!("{}", err);
+                emit!(warn, err);
                 None
             }
         }
     }
 
     #[inline]
+    #[track_caller]
     fn consume_as_info(self) -> Option<T> {
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                defmt::info!("{}", err);
+                emit!(info, err);
                 None
             }
         }
     }
 
     #[inline]
+    #[track_caller]
     fn consume_as_debug(self) -> Option<T> {
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                defmt::debug!("{}", err);
+                emit!(debug, err);
                 None
             }
         }
     }
 
     #[inline]
+    #[track_caller]
     fn consume_as_trace(self) -> Option<T> {
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                defmt::trace!("{}", err);
+                emit!(trace, err);
                 None
             }
         }
@@ -292,82 +357,447 @@ impl<T, E: Format> ErrContextDisplayDefmt<T, E> for Result<T, E> {
 impl<T> sealed::Sealed for Option<T> {}
 impl<T> NoneContextDefmt<T> for Option<T> {
     #[inline]
+    #[track_caller]
     fn error_context(self, context: impl Format) -> Option<T> {
         if self.is_none() {
-            defmt::error!("{}", context);
+            emit!(error, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn warn_context(self, context: impl Format) -> Option<T> {
         if self.is_none() {
-            defmt::warn!("{}", context);
+            emit!(warn, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn info_context(self, context: impl Format) -> Option<T> {
         if self.is_none() {
-            defmt::info!("{}", context);
+            emit!(info, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn debug_context(self, context: impl Format) -> Option<T> {
         if self.is_none() {
-            defmt::debug!("{}", context);
+            emit!(debug, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn trace_context(self, context: impl Format) -> Option<T> {
         if self.is_none() {
-            defmt::trace!("{}", context);
+            emit!(trace, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_error_context<F: FnOnce() -> D, D: Format>(self, f: F) -> Option<T> {
         if self.is_none() {
-            defmt::error!("{}", f());
+            emit!(error, f());
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_warn_context<F: FnOnce() -> D, D: Format>(self, f: F) -> Option<T> {
         if self.is_none() {
-            defmt::warn!("{}", f());
+            emit!(warn, f());
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_info_context<F: FnOnce() -> D, D: Format>(self, f: F) -> Option<T> {
         if self.is_none() {
-            defmt::info!("{}", f());
+            emit!(info, f());
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_debug_context<F: FnOnce() -> D, D: Format>(self, f: F) -> Option<T> {
         if self.is_none() {
-            defmt::debug!("{}", f());
+            emit!(debug, f());
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_trace_context<F: FnOnce() -> D, D: Format>(self, f: F) -> Option<T> {
         if self.is_none() {
-            defmt::trace!("{}", f());
+            emit!(trace, f());
         }
         self
     }
-} 
+}
+
+//************************************************************************//
+
+/// For logging the [Err] items of an [Iterator], e.g. `results.skip_errs_warn(|err| format!("Skipped: {err}"))`.
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(
+        feature = "defmt",
+        not(any(feature = "tracing", feature = "log", feature = "sink"))
+    )))
+)]
+pub trait ErrContextIterDefmt<T, E>: Iterator<Item = Result<T, E>> + Sized {
+    /// Lazily logs the result of [f] as an "error" for each [Err] item, passing the items through.
+    fn log_errs_error<F: FnMut(&E) -> D, D: Format>(self, f: F) -> LogErrsDefmt<Self, F>;
+    /// Lazily logs the result of [f] as a "warn" for each [Err] item, passing the items through.
+    fn log_errs_warn<F: FnMut(&E) -> D, D: Format>(self, f: F) -> LogErrsDefmt<Self, F>;
+    /// Lazily logs the result of [f] as an "info" for each [Err] item, passing the items through.
+    fn log_errs_info<F: FnMut(&E) -> D, D: Format>(self, f: F) -> LogErrsDefmt<Self, F>;
+    /// Lazily logs the result of [f] as a "debug" for each [Err] item, passing the items through.
+    fn log_errs_debug<F: FnMut(&E) -> D, D: Format>(self, f: F) -> LogErrsDefmt<Self, F>;
+    /// Lazily logs the result of [f] as a "trace" for each [Err] item, passing the items through.
+    fn log_errs_trace<F: FnMut(&E) -> D, D: Format>(self, f: F) -> LogErrsDefmt<Self, F>;
+
+    /// Consumes the [Err] items. Lazily logs the result of [f] as an "error" for each, and skips it.
+    fn skip_errs_error<F: FnMut(&E) -> D, D: Format>(self, f: F) -> SkipErrsDefmt<Self, F>;
+    /// Consumes the [Err] items. Lazily logs the result of [f] as a "warn" for each, and skips it.
+    fn skip_errs_warn<F: FnMut(&E) -> D, D: Format>(self, f: F) -> SkipErrsDefmt<Self, F>;
+    /// Consumes the [Err] items. Lazily logs the result of [f] as an "info" for each, and skips it.
+    fn skip_errs_info<F: FnMut(&E) -> D, D: Format>(self, f: F) -> SkipErrsDefmt<Self, F>;
+    /// Consumes the [Err] items. Lazily logs the result of [f] as a "debug" for each, and skips it.
+    fn skip_errs_debug<F: FnMut(&E) -> D, D: Format>(self, f: F) -> SkipErrsDefmt<Self, F>;
+    /// Consumes the [Err] items. Lazily logs the result of [f] as a "trace" for each, and skips it.
+    fn skip_errs_trace<F: FnMut(&E) -> D, D: Format>(self, f: F) -> SkipErrsDefmt<Self, F>;
+
+    /// Partitions the items into the [Ok] values and the [Err]s. Lazily logs the result of [f] as an "error" for each
+    /// [Err].
+    fn partition_logged_error<
+        F: FnMut(&E) -> D,
+        D: Format,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B);
+    /// Partitions the items into the [Ok] values and the [Err]s. Lazily logs the result of [f] as a "warn" for each
+    /// [Err].
+    fn partition_logged_warn<
+        F: FnMut(&E) -> D,
+        D: Format,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B);
+    /// Partitions the items into the [Ok] values and the [Err]s. Lazily logs the result of [f] as an "info" for each
+    /// [Err].
+    fn partition_logged_info<
+        F: FnMut(&E) -> D,
+        D: Format,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B);
+    /// Partitions the items into the [Ok] values and the [Err]s. Lazily logs the result of [f] as a "debug" for each
+    /// [Err].
+    fn partition_logged_debug<
+        F: FnMut(&E) -> D,
+        D: Format,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B);
+    /// Partitions the items into the [Ok] values and the [Err]s. Lazily logs the result of [f] as a "trace" for each
+    /// [Err].
+    fn partition_logged_trace<
+        F: FnMut(&E) -> D,
+        D: Format,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B);
+}
+
+impl<I: Iterator<Item = Result<T, E>>, T, E> ErrContextIterDefmt<T, E> for I {
+    #[inline]
+    #[track_caller]
+    fn log_errs_error<F: FnMut(&E) -> D, D: Format>(self, f: F) -> LogErrsDefmt<Self, F> {
+        LogErrsDefmt {
+            iter: self,
+            f,
+            level: Level::Error,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn log_errs_warn<F: FnMut(&E) -> D, D: Format>(self, f: F) -> LogErrsDefmt<Self, F> {
+        LogErrsDefmt {
+            iter: self,
+            f,
+            level: Level::Warn,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn log_errs_info<F: FnMut(&E) -> D, D: Format>(self, f: F) -> LogErrsDefmt<Self, F> {
+        LogErrsDefmt {
+            iter: self,
+            f,
+            level: Level::Info,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn log_errs_debug<F: FnMut(&E) -> D, D: Format>(self, f: F) -> LogErrsDefmt<Self, F> {
+        LogErrsDefmt {
+            iter: self,
+            f,
+            level: Level::Debug,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn log_errs_trace<F: FnMut(&E) -> D, D: Format>(self, f: F) -> LogErrsDefmt<Self, F> {
+        LogErrsDefmt {
+            iter: self,
+            f,
+            level: Level::Trace,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn skip_errs_error<F: FnMut(&E) -> D, D: Format>(self, f: F) -> SkipErrsDefmt<Self, F> {
+        SkipErrsDefmt {
+            iter: self,
+            f,
+            level: Level::Error,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn skip_errs_warn<F: FnMut(&E) -> D, D: Format>(self, f: F) -> SkipErrsDefmt<Self, F> {
+        SkipErrsDefmt {
+            iter: self,
+            f,
+            level: Level::Warn,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn skip_errs_info<F: FnMut(&E) -> D, D: Format>(self, f: F) -> SkipErrsDefmt<Self, F> {
+        SkipErrsDefmt {
+            iter: self,
+            f,
+            level: Level::Info,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn skip_errs_debug<F: FnMut(&E) -> D, D: Format>(self, f: F) -> SkipErrsDefmt<Self, F> {
+        SkipErrsDefmt {
+            iter: self,
+            f,
+            level: Level::Debug,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn skip_errs_trace<F: FnMut(&E) -> D, D: Format>(self, f: F) -> SkipErrsDefmt<Self, F> {
+        SkipErrsDefmt {
+            iter: self,
+            f,
+            level: Level::Trace,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn partition_logged_error<
+        F: FnMut(&E) -> D,
+        D: Format,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B) {
+        partition_logged(self, f, Level::Error, Location::caller())
+    }
+
+    #[inline]
+    #[track_caller]
+    fn partition_logged_warn<
+        F: FnMut(&E) -> D,
+        D: Format,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B) {
+        partition_logged(self, f, Level::Warn, Location::caller())
+    }
+
+    #[inline]
+    #[track_caller]
+    fn partition_logged_info<
+        F: FnMut(&E) -> D,
+        D: Format,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B) {
+        partition_logged(self, f, Level::Info, Location::caller())
+    }
+
+    #[inline]
+    #[track_caller]
+    fn partition_logged_debug<
+        F: FnMut(&E) -> D,
+        D: Format,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B) {
+        partition_logged(self, f, Level::Debug, Location::caller())
+    }
+
+    #[inline]
+    #[track_caller]
+    fn partition_logged_trace<
+        F: FnMut(&E) -> D,
+        D: Format,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B) {
+        partition_logged(self, f, Level::Trace, Location::caller())
+    }
+}
+
+fn partition_logged<
+    I: Iterator<Item = Result<T, E>>,
+    T,
+    E,
+    F: FnMut(&E) -> D,
+    D: Format,
+    A: Default + Extend<T>,
+    B: Default + Extend<E>,
+>(
+    iter: I,
+    mut f: F,
+    level: Level,
+    location: &'static Location<'static>,
+) -> (A, B) {
+    let mut values = A::default();
+    let mut errors = B::default();
+    for item in iter {
+        match item {
+            Ok(value) => values.extend(Some(value)),
+            Err(err) => {
+                emit_at(level, &f(&err), location);
+                errors.extend(Some(err));
+            }
+        }
+    }
+    (values, errors)
+}
+
+/// The [Iterator] of the [ErrContextIterDefmt] `log_errs_*` methods.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct LogErrsDefmt<I, F> {
+    iter: I,
+    f: F,
+    level: Level,
+    location: &'static Location<'static>,
+}
+
+impl<I: Iterator<Item = Result<T, E>>, T, E, F: FnMut(&E) -> D, D: Format> Iterator
+    for LogErrsDefmt<I, F>
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        if let Err(err) = &item {
+            emit_at(self.level, &(self.f)(err), self.location);
+        }
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// The [Iterator] of the [ErrContextIterDefmt] `skip_errs_*` methods, which yields the [Ok] values.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SkipErrsDefmt<I, F> {
+    iter: I,
+    f: F,
+    level: Level,
+    location: &'static Location<'static>,
+}
+
+impl<I: Iterator<Item = Result<T, E>>, T, E, F: FnMut(&E) -> D, D: Format> Iterator
+    for SkipErrsDefmt<I, F>
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.iter.next()? {
+                Ok(value) => return Some(value),
+                Err(err) => emit_at(self.level, &(self.f)(&err), self.location),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
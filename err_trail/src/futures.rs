@@ -0,0 +1,560 @@
+use core::fmt::Display;
+use core::future::Future;
+use core::panic::Location;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use crate::backend::route_at;
+use crate::Level;
+
+/// For logging the output of a [Future] when it is [Err], e.g.
+/// `fetch(url).warn_context("Could not fetch the manifest").await`.
+#[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+pub trait FutureErrContext<T, E>: Future<Output = Result<T, E>> + Sized {
+    /// If the output is [Err], logging context as an "error".
+    fn error_context<D: Display>(self, context: D) -> ErrContextFuture<Self, D>;
+    /// If the output is [Err], logging context as a "warn".
+    fn warn_context<D: Display>(self, context: D) -> ErrContextFuture<Self, D>;
+    /// If the output is [Err], logging context as an "info".
+    fn info_context<D: Display>(self, context: D) -> ErrContextFuture<Self, D>;
+    /// If the output is [Err], logging context as a "debug".
+    fn debug_context<D: Display>(self, context: D) -> ErrContextFuture<Self, D>;
+    /// If the output is [Err], logging context as a "trace".
+    fn trace_context<D: Display>(self, context: D) -> ErrContextFuture<Self, D>;
+
+    /// If the output is [Err], lazily logging the result of [f] as an "error".
+    fn with_error_context<F: FnOnce(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextFuture<Self, F>;
+    /// If the output is [Err], lazily logging the result of [f] as a "warn".
+    fn with_warn_context<F: FnOnce(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextFuture<Self, F>;
+    /// If the output is [Err], lazily logging the result of [f] as an "info".
+    fn with_info_context<F: FnOnce(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextFuture<Self, F>;
+    /// If the output is [Err], lazily logging the result of [f] as a "debug".
+    fn with_debug_context<F: FnOnce(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextFuture<Self, F>;
+    /// If the output is [Err], lazily logging the result of [f] as a "trace".
+    fn with_trace_context<F: FnOnce(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextFuture<Self, F>;
+}
+
+/// For logging the [Err] items of a [Stream], e.g.
+/// `uploads.warn_context("Upload failed")` or `uploads.consume_with_warn(|err| format!("Upload failed: {err}"))`.
+#[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+pub trait StreamErrContext<T, E>: Stream<Item = Result<T, E>> + Sized {
+    /// Logs context as an "error" for each [Err] item, passing the items through.
+    fn error_context<D: Display>(self, context: D) -> ErrContextStream<Self, D>;
+    /// Logs context as a "warn" for each [Err] item, passing the items through.
+    fn warn_context<D: Display>(self, context: D) -> ErrContextStream<Self, D>;
+    /// Logs context as an "info" for each [Err] item, passing the items through.
+    fn info_context<D: Display>(self, context: D) -> ErrContextStream<Self, D>;
+    /// Logs context as a "debug" for each [Err] item, passing the items through.
+    fn debug_context<D: Display>(self, context: D) -> ErrContextStream<Self, D>;
+    /// Logs context as a "trace" for each [Err] item, passing the items through.
+    fn trace_context<D: Display>(self, context: D) -> ErrContextStream<Self, D>;
+
+    /// Lazily logs the result of [f] as an "error" for each [Err] item, passing the items through.
+    fn with_error_context<F: FnMut(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextStream<Self, F>;
+    /// Lazily logs the result of [f] as a "warn" for each [Err] item, passing the items through.
+    fn with_warn_context<F: FnMut(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextStream<Self, F>;
+    /// Lazily logs the result of [f] as an "info" for each [Err] item, passing the items through.
+    fn with_info_context<F: FnMut(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextStream<Self, F>;
+    /// Lazily logs the result of [f] as a "debug" for each [Err] item, passing the items through.
+    fn with_debug_context<F: FnMut(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextStream<Self, F>;
+    /// Lazily logs the result of [f] as a "trace" for each [Err] item, passing the items through.
+    fn with_trace_context<F: FnMut(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextStream<Self, F>;
+
+    /// Consumes the [Err] items. Lazily logs the result of [f] as an "error" for each, and skips it.
+    fn consume_with_error<F: FnMut(&E) -> D, D: Display>(self, f: F) -> ConsumeWithStream<Self, F>;
+    /// Consumes the [Err] items. Lazily logs the result of [f] as a "warn" for each, and skips it.
+    fn consume_with_warn<F: FnMut(&E) -> D, D: Display>(self, f: F) -> ConsumeWithStream<Self, F>;
+    /// Consumes the [Err] items. Lazily logs the result of [f] as an "info" for each, and skips it.
+    fn consume_with_info<F: FnMut(&E) -> D, D: Display>(self, f: F) -> ConsumeWithStream<Self, F>;
+    /// Consumes the [Err] items. Lazily logs the result of [f] as a "debug" for each, and skips it.
+    fn consume_with_debug<F: FnMut(&E) -> D, D: Display>(self, f: F) -> ConsumeWithStream<Self, F>;
+    /// Consumes the [Err] items. Lazily logs the result of [f] as a "trace" for each, and skips it.
+    fn consume_with_trace<F: FnMut(&E) -> D, D: Display>(self, f: F) -> ConsumeWithStream<Self, F>;
+}
+
+impl<Fut: Future<Output = Result<T, E>>, T, E> FutureErrContext<T, E> for Fut {
+    #[inline]
+    #[track_caller]
+    fn error_context<D: Display>(self, context: D) -> ErrContextFuture<Self, D> {
+        ErrContextFuture {
+            future: self,
+            context,
+            level: Level::Error,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn warn_context<D: Display>(self, context: D) -> ErrContextFuture<Self, D> {
+        ErrContextFuture {
+            future: self,
+            context,
+            level: Level::Warn,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn info_context<D: Display>(self, context: D) -> ErrContextFuture<Self, D> {
+        ErrContextFuture {
+            future: self,
+            context,
+            level: Level::Info,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn debug_context<D: Display>(self, context: D) -> ErrContextFuture<Self, D> {
+        ErrContextFuture {
+            future: self,
+            context,
+            level: Level::Debug,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn trace_context<D: Display>(self, context: D) -> ErrContextFuture<Self, D> {
+        ErrContextFuture {
+            future: self,
+            context,
+            level: Level::Trace,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn with_error_context<F: FnOnce(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextFuture<Self, F> {
+        WithErrContextFuture {
+            future: self,
+            f: Some(f),
+            level: Level::Error,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn with_warn_context<F: FnOnce(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextFuture<Self, F> {
+        WithErrContextFuture {
+            future: self,
+            f: Some(f),
+            level: Level::Warn,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn with_info_context<F: FnOnce(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextFuture<Self, F> {
+        WithErrContextFuture {
+            future: self,
+            f: Some(f),
+            level: Level::Info,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn with_debug_context<F: FnOnce(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextFuture<Self, F> {
+        WithErrContextFuture {
+            future: self,
+            f: Some(f),
+            level: Level::Debug,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn with_trace_context<F: FnOnce(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextFuture<Self, F> {
+        WithErrContextFuture {
+            future: self,
+            f: Some(f),
+            level: Level::Trace,
+            location: Location::caller(),
+        }
+    }
+}
+
+impl<S: Stream<Item = Result<T, E>>, T, E> StreamErrContext<T, E> for S {
+    #[inline]
+    #[track_caller]
+    fn error_context<D: Display>(self, context: D) -> ErrContextStream<Self, D> {
+        ErrContextStream {
+            stream: self,
+            context,
+            level: Level::Error,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn warn_context<D: Display>(self, context: D) -> ErrContextStream<Self, D> {
+        ErrContextStream {
+            stream: self,
+            context,
+            level: Level::Warn,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn info_context<D: Display>(self, context: D) -> ErrContextStream<Self, D> {
+        ErrContextStream {
+            stream: self,
+            context,
+            level: Level::Info,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn debug_context<D: Display>(self, context: D) -> ErrContextStream<Self, D> {
+        ErrContextStream {
+            stream: self,
+            context,
+            level: Level::Debug,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn trace_context<D: Display>(self, context: D) -> ErrContextStream<Self, D> {
+        ErrContextStream {
+            stream: self,
+            context,
+            level: Level::Trace,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn with_error_context<F: FnMut(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextStream<Self, F> {
+        WithErrContextStream {
+            stream: self,
+            f,
+            level: Level::Error,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn with_warn_context<F: FnMut(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextStream<Self, F> {
+        WithErrContextStream {
+            stream: self,
+            f,
+            level: Level::Warn,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn with_info_context<F: FnMut(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextStream<Self, F> {
+        WithErrContextStream {
+            stream: self,
+            f,
+            level: Level::Info,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn with_debug_context<F: FnMut(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextStream<Self, F> {
+        WithErrContextStream {
+            stream: self,
+            f,
+            level: Level::Debug,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn with_trace_context<F: FnMut(&E) -> D, D: Display>(
+        self,
+        f: F,
+    ) -> WithErrContextStream<Self, F> {
+        WithErrContextStream {
+            stream: self,
+            f,
+            level: Level::Trace,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn consume_with_error<F: FnMut(&E) -> D, D: Display>(self, f: F) -> ConsumeWithStream<Self, F> {
+        ConsumeWithStream {
+            stream: self,
+            f,
+            level: Level::Error,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn consume_with_warn<F: FnMut(&E) -> D, D: Display>(self, f: F) -> ConsumeWithStream<Self, F> {
+        ConsumeWithStream {
+            stream: self,
+            f,
+            level: Level::Warn,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn consume_with_info<F: FnMut(&E) -> D, D: Display>(self, f: F) -> ConsumeWithStream<Self, F> {
+        ConsumeWithStream {
+            stream: self,
+            f,
+            level: Level::Info,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn consume_with_debug<F: FnMut(&E) -> D, D: Display>(self, f: F) -> ConsumeWithStream<Self, F> {
+        ConsumeWithStream {
+            stream: self,
+            f,
+            level: Level::Debug,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn consume_with_trace<F: FnMut(&E) -> D, D: Display>(self, f: F) -> ConsumeWithStream<Self, F> {
+        ConsumeWithStream {
+            stream: self,
+            f,
+            level: Level::Trace,
+            location: Location::caller(),
+        }
+    }
+}
+
+pin_project! {
+    /// The [Future] of the [FutureErrContext] `*_context` methods.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct ErrContextFuture<Fut, D> {
+        #[pin]
+        future: Fut,
+        context: D,
+        level: Level,
+        location: &'static Location<'static>,
+    }
+}
+
+impl<Fut: Future<Output = Result<T, E>>, T, E, D: Display> Future for ErrContextFuture<Fut, D> {
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let result = ready!(this.future.poll(cx));
+        if result.is_err() {
+            route_at(*this.level, this.context, this.location);
+        }
+        Poll::Ready(result)
+    }
+}
+
+pin_project! {
+    /// The [Future] of the [FutureErrContext] `with_*_context` methods.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct WithErrContextFuture<Fut, F> {
+        #[pin]
+        future: Fut,
+        f: Option<F>,
+        level: Level,
+        location: &'static Location<'static>,
+    }
+}
+
+impl<Fut: Future<Output = Result<T, E>>, T, E, F: FnOnce(&E) -> D, D: Display> Future
+    for WithErrContextFuture<Fut, F>
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let result = ready!(this.future.poll(cx));
+        if let (Err(err), Some(f)) = (&result, this.f.take()) {
+            route_at(*this.level, &f(err), this.location);
+        }
+        Poll::Ready(result)
+    }
+}
+
+pin_project! {
+    /// The [Stream] of the [StreamErrContext] `*_context` methods.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct ErrContextStream<S, D> {
+        #[pin]
+        stream: S,
+        context: D,
+        level: Level,
+        location: &'static Location<'static>,
+    }
+}
+
+impl<S: Stream<Item = Result<T, E>>, T, E, D: Display> Stream for ErrContextStream<S, D> {
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let item = ready!(this.stream.poll_next(cx));
+        if let Some(Err(_)) = &item {
+            route_at(*this.level, this.context, this.location);
+        }
+        Poll::Ready(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+pin_project! {
+    /// The [Stream] of the [StreamErrContext] `with_*_context` methods.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct WithErrContextStream<S, F> {
+        #[pin]
+        stream: S,
+        f: F,
+        level: Level,
+        location: &'static Location<'static>,
+    }
+}
+
+impl<S: Stream<Item = Result<T, E>>, T, E, F: FnMut(&E) -> D, D: Display> Stream
+    for WithErrContextStream<S, F>
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let item = ready!(this.stream.poll_next(cx));
+        if let Some(Err(err)) = &item {
+            route_at(*this.level, &(this.f)(err), this.location);
+        }
+        Poll::Ready(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+pin_project! {
+    /// The [Stream] of the [StreamErrContext] `consume_with_*` methods, which yields the [Ok] values.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct ConsumeWithStream<S, F> {
+        #[pin]
+        stream: S,
+        f: F,
+        level: Level,
+        location: &'static Location<'static>,
+    }
+}
+
+impl<S: Stream<Item = Result<T, E>>, T, E, F: FnMut(&E) -> D, D: Display> Stream
+    for ConsumeWithStream<S, F>
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(Ok(value)) => return Poll::Ready(Some(value)),
+                Some(Err(err)) => route_at(*this.level, &(this.f)(&err), this.location),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.stream.size_hint().1)
+    }
+}
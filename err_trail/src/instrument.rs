@@ -0,0 +1,172 @@
+//! Support for the `#[instrument_err]` attribute. The backend is chosen here, rather than in the expansion, so the
+//! attribute follows the features `err_trail` is compiled with.
+
+#![allow(unused_variables)]
+
+use core::error::Error;
+use core::fmt::{self, Debug, Display, Write};
+use core::panic::Location;
+
+use crate::{DisplayChain, Level};
+
+/// The error returned from an instrumented function. Displayed with its sources when it is an [Error], through
+/// autoref specialization on [ViaError] and [ViaDisplay].
+pub struct Chain<'a, E: ?Sized>(pub &'a E);
+
+pub trait ViaError<'a, E: ?Sized> {
+    fn __err_trail_display(&self) -> DisplayChain<'a, E>;
+}
+
+impl<'a, E: Error + ?Sized> ViaError<'a, E> for &&Chain<'a, E> {
+    #[inline]
+    fn __err_trail_display(&self) -> DisplayChain<'a, E> {
+        DisplayChain::new(self.0)
+    }
+}
+
+pub trait ViaDisplay<'a, E: ?Sized> {
+    fn __err_trail_display(&self) -> &'a E;
+}
+
+impl<'a, E: Display + ?Sized> ViaDisplay<'a, E> for &Chain<'a, E> {
+    #[inline]
+    fn __err_trail_display(&self) -> &'a E {
+        self.0
+    }
+}
+
+/// Calls the body of a sync function. Passing the closure as [FnOnce] lets the body return borrows of `&mut` arguments.
+#[inline(always)]
+pub fn call_once<R>(body: impl FnOnce() -> R) -> R {
+    body()
+}
+
+/// The captured arguments, formatted as ` name=value` before the function runs, since the body may move or mutably
+/// borrow them. A stack buffer, so it works without an allocator. Longer fields are truncated and end with `...`.
+pub struct Fields {
+    buffer: [u8; Fields::CAPACITY],
+    len: usize,
+    truncated: bool,
+}
+
+impl Fields {
+    const CAPACITY: usize = 256;
+
+    pub fn new(fields: &[(&str, &dyn Debug)]) -> Self {
+        let mut this = Fields {
+            buffer: [0; Fields::CAPACITY],
+            len: 0,
+            truncated: false,
+        };
+        for (name, value) in fields {
+            if write!(this, " {}={:?}", name, value).is_err() {
+                break;
+            }
+        }
+        this
+    }
+}
+
+impl fmt::Write for Fields {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let available = Fields::CAPACITY - self.len;
+        if s.len() <= available {
+            self.buffer[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+            self.len += s.len();
+            return Ok(());
+        }
+        let mut end = available;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.buffer[self.len..self.len + end].copy_from_slice(&s.as_bytes()[..end]);
+        self.len += end;
+        self.truncated = true;
+        Err(fmt::Error)
+    }
+}
+
+impl Display for Fields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only whole characters are written to the buffer
+        f.write_str(core::str::from_utf8(&self.buffer[..self.len]).unwrap_or_default())?;
+        if self.truncated {
+            f.write_str("...")?;
+        }
+        Ok(())
+    }
+}
+
+/// "`function` failed" followed by the captured arguments, e.g. "`load_cover` failed path=\"cover.png\"".
+struct Message<'a> {
+    function: &'a str,
+    fields: Option<&'a Fields>,
+}
+
+impl Display for Message<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` failed", self.function)?;
+        if let Some(fields) = self.fields {
+            write!(f, "{}", fields)?;
+        }
+        Ok(())
+    }
+}
+
+/// Logs the error returned from `function`.
+pub fn log(
+    level: Level,
+    function: &str,
+    error: &dyn Display,
+    fields: Option<&Fields>,
+    location: &'static Location<'static>,
+) {
+    let message = Message { function, fields };
+    #[cfg(any(feature = "tracing", feature = "log", feature = "sink"))]
+    crate::backend::route_error_at(level, &message, error, location);
+    #[cfg(all(
+        feature = "defmt",
+        not(any(feature = "tracing", feature = "log", feature = "sink"))
+    ))]
+    {
+        use defmt::Display2Format;
+        let (message, error) = (Display2Format(&message), Display2Format(error));
+        match level {
+            Level::Error => defmt::error!(
+                "{}: {} ({=str}:{=u32})",
+                message,
+                error,
+                location.file(),
+                location.line()
+            ),
+            Level::Warn => defmt::warn!(
+                "{}: {} ({=str}:{=u32})",
+                message,
+                error,
+                location.file(),
+                location.line()
+            ),
+            Level::Info => defmt::info!(
+                "{}: {} ({=str}:{=u32})",
+                message,
+                error,
+                location.file(),
+                location.line()
+            ),
+            Level::Debug => defmt::debug!(
+                "{}: {} ({=str}:{=u32})",
+                message,
+                error,
+                location.file(),
+                location.line()
+            ),
+            Level::Trace => defmt::trace!(
+                "{}: {} ({=str}:{=u32})",
+                message,
+                error,
+                location.file(),
+                location.line()
+            ),
+        }
+    }
+}
@@ -0,0 +1,360 @@
+use core::fmt::Display;
+use core::panic::Location;
+
+use crate::backend::route_at;
+use crate::Level;
+
+/// For logging the [Err] items of an [Iterator], e.g. `results.skip_errs_warn(|err| format!("Skipped: {err}"))`.
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(
+        feature = "tracing",
+        feature = "log",
+        feature = "sink",
+        all(feature = "stub", not(feature = "defmt"))
+    )))
+)]
+pub trait ErrContextIter<T, E>: Iterator<Item = Result<T, E>> + Sized {
+    /// Lazily logs the result of [f] as an "error" for each [Err] item, passing the items through.
+    fn log_errs_error<F: FnMut(&E) -> D, D: Display>(self, f: F) -> LogErrs<Self, F>;
+    /// Lazily logs the result of [f] as a "warn" for each [Err] item, passing the items through.
+    fn log_errs_warn<F: FnMut(&E) -> D, D: Display>(self, f: F) -> LogErrs<Self, F>;
+    /// Lazily logs the result of [f] as an "info" for each [Err] item, passing the items through.
+    fn log_errs_info<F: FnMut(&E) -> D, D: Display>(self, f: F) -> LogErrs<Self, F>;
+    /// Lazily logs the result of [f] as a "debug" for each [Err] item, passing the items through.
+    fn log_errs_debug<F: FnMut(&E) -> D, D: Display>(self, f: F) -> LogErrs<Self, F>;
+    /// Lazily logs the result of [f] as a "trace" for each [Err] item, passing the items through.
+    fn log_errs_trace<F: FnMut(&E) -> D, D: Display>(self, f: F) -> LogErrs<Self, F>;
+
+    /// Consumes the [Err] items. Lazily logs the result of [f] as an "error" for each, and skips it.
+    fn skip_errs_error<F: FnMut(&E) -> D, D: Display>(self, f: F) -> SkipErrs<Self, F>;
+    /// Consumes the [Err] items. Lazily logs the result of [f] as a "warn" for each, and skips it.
+    fn skip_errs_warn<F: FnMut(&E) -> D, D: Display>(self, f: F) -> SkipErrs<Self, F>;
+    /// Consumes the [Err] items. Lazily logs the result of [f] as an "info" for each, and skips it.
+    fn skip_errs_info<F: FnMut(&E) -> D, D: Display>(self, f: F) -> SkipErrs<Self, F>;
+    /// Consumes the [Err] items. Lazily logs the result of [f] as a "debug" for each, and skips it.
+    fn skip_errs_debug<F: FnMut(&E) -> D, D: Display>(self, f: F) -> SkipErrs<Self, F>;
+    /// Consumes the [Err] items. Lazily logs the result of [f] as a "trace" for each, and skips it.
+    fn skip_errs_trace<F: FnMut(&E) -> D, D: Display>(self, f: F) -> SkipErrs<Self, F>;
+
+    /// Partitions the items into the [Ok] values and the [Err]s. Lazily logs the result of [f] as an "error" for each
+    /// [Err].
+    fn partition_logged_error<
+        F: FnMut(&E) -> D,
+        D: Display,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B);
+    /// Partitions the items into the [Ok] values and the [Err]s. Lazily logs the result of [f] as a "warn" for each
+    /// [Err].
+    fn partition_logged_warn<
+        F: FnMut(&E) -> D,
+        D: Display,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B);
+    /// Partitions the items into the [Ok] values and the [Err]s. Lazily logs the result of [f] as an "info" for each
+    /// [Err].
+    fn partition_logged_info<
+        F: FnMut(&E) -> D,
+        D: Display,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B);
+    /// Partitions the items into the [Ok] values and the [Err]s. Lazily logs the result of [f] as a "debug" for each
+    /// [Err].
+    fn partition_logged_debug<
+        F: FnMut(&E) -> D,
+        D: Display,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B);
+    /// Partitions the items into the [Ok] values and the [Err]s. Lazily logs the result of [f] as a "trace" for each
+    /// [Err].
+    fn partition_logged_trace<
+        F: FnMut(&E) -> D,
+        D: Display,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B);
+}
+
+impl<I: Iterator<Item = Result<T, E>>, T, E> ErrContextIter<T, E> for I {
+    #[inline]
+    #[track_caller]
+    fn log_errs_error<F: FnMut(&E) -> D, D: Display>(self, f: F) -> LogErrs<Self, F> {
+        LogErrs {
+            iter: self,
+            f,
+            level: Level::Error,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn log_errs_warn<F: FnMut(&E) -> D, D: Display>(self, f: F) -> LogErrs<Self, F> {
+        LogErrs {
+            iter: self,
+            f,
+            level: Level::Warn,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn log_errs_info<F: FnMut(&E) -> D, D: Display>(self, f: F) -> LogErrs<Self, F> {
+        LogErrs {
+            iter: self,
+            f,
+            level: Level::Info,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn log_errs_debug<F: FnMut(&E) -> D, D: Display>(self, f: F) -> LogErrs<Self, F> {
+        LogErrs {
+            iter: self,
+            f,
+            level: Level::Debug,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn log_errs_trace<F: FnMut(&E) -> D, D: Display>(self, f: F) -> LogErrs<Self, F> {
+        LogErrs {
+            iter: self,
+            f,
+            level: Level::Trace,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn skip_errs_error<F: FnMut(&E) -> D, D: Display>(self, f: F) -> SkipErrs<Self, F> {
+        SkipErrs {
+            iter: self,
+            f,
+            level: Level::Error,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn skip_errs_warn<F: FnMut(&E) -> D, D: Display>(self, f: F) -> SkipErrs<Self, F> {
+        SkipErrs {
+            iter: self,
+            f,
+            level: Level::Warn,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn skip_errs_info<F: FnMut(&E) -> D, D: Display>(self, f: F) -> SkipErrs<Self, F> {
+        SkipErrs {
+            iter: self,
+            f,
+            level: Level::Info,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn skip_errs_debug<F: FnMut(&E) -> D, D: Display>(self, f: F) -> SkipErrs<Self, F> {
+        SkipErrs {
+            iter: self,
+            f,
+            level: Level::Debug,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn skip_errs_trace<F: FnMut(&E) -> D, D: Display>(self, f: F) -> SkipErrs<Self, F> {
+        SkipErrs {
+            iter: self,
+            f,
+            level: Level::Trace,
+            location: Location::caller(),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn partition_logged_error<
+        F: FnMut(&E) -> D,
+        D: Display,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B) {
+        partition_logged(self, f, Level::Error, Location::caller())
+    }
+
+    #[inline]
+    #[track_caller]
+    fn partition_logged_warn<
+        F: FnMut(&E) -> D,
+        D: Display,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B) {
+        partition_logged(self, f, Level::Warn, Location::caller())
+    }
+
+    #[inline]
+    #[track_caller]
+    fn partition_logged_info<
+        F: FnMut(&E) -> D,
+        D: Display,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B) {
+        partition_logged(self, f, Level::Info, Location::caller())
+    }
+
+    #[inline]
+    #[track_caller]
+    fn partition_logged_debug<
+        F: FnMut(&E) -> D,
+        D: Display,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B) {
+        partition_logged(self, f, Level::Debug, Location::caller())
+    }
+
+    #[inline]
+    #[track_caller]
+    fn partition_logged_trace<
+        F: FnMut(&E) -> D,
+        D: Display,
+        A: Default + Extend<T>,
+        B: Default + Extend<E>,
+    >(
+        self,
+        f: F,
+    ) -> (A, B) {
+        partition_logged(self, f, Level::Trace, Location::caller())
+    }
+}
+
+fn partition_logged<
+    I: Iterator<Item = Result<T, E>>,
+    T,
+    E,
+    F: FnMut(&E) -> D,
+    D: Display,
+    A: Default + Extend<T>,
+    B: Default + Extend<E>,
+>(
+    iter: I,
+    mut f: F,
+    level: Level,
+    location: &'static Location<'static>,
+) -> (A, B) {
+    let mut values = A::default();
+    let mut errors = B::default();
+    for item in iter {
+        match item {
+            Ok(value) => values.extend(Some(value)),
+            Err(err) => {
+                route_at(level, &f(&err), location);
+                errors.extend(Some(err));
+            }
+        }
+    }
+    (values, errors)
+}
+
+/// The [Iterator] of the [ErrContextIter] `log_errs_*` methods.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct LogErrs<I, F> {
+    iter: I,
+    f: F,
+    level: Level,
+    location: &'static Location<'static>,
+}
+
+impl<I: Iterator<Item = Result<T, E>>, T, E, F: FnMut(&E) -> D, D: Display> Iterator
+    for LogErrs<I, F>
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        if let Err(err) = &item {
+            route_at(self.level, &(self.f)(err), self.location);
+        }
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// The [Iterator] of the [ErrContextIter] `skip_errs_*` methods, which yields the [Ok] values.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SkipErrs<I, F> {
+    iter: I,
+    f: F,
+    level: Level,
+    location: &'static Location<'static>,
+}
+
+impl<I: Iterator<Item = Result<T, E>>, T, E, F: FnMut(&E) -> D, D: Display> Iterator
+    for SkipErrs<I, F>
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.iter.next()? {
+                Ok(value) => return Some(value),
+                Err(err) => route_at(self.level, &(self.f)(&err), self.location),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
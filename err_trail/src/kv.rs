@@ -0,0 +1,169 @@
+use core::fmt::Display;
+
+use crate::Level;
+
+/// Structured key-value fields for the `*_context_kv` methods. Created with [fields!](crate::fields).
+pub struct Fields<F: FieldsFn>(#[doc(hidden)] pub F);
+
+/// Logs the context, the error, and the fields of a [Fields]. Implemented by the closure created by
+/// [fields!](crate::fields).
+pub trait FieldsFn: FnOnce(Level, &dyn Display, Option<&dyn Display>) {}
+
+impl<F: FnOnce(Level, &dyn Display, Option<&dyn Display>)> FieldsFn for F {}
+
+/// Creates [Fields] that are emitted as `tracing` fields, or `log` key-value pairs. A value is a
+/// [tracing::Value](https://docs.rs/tracing/latest/tracing/trait.Value.html) or
+/// [log::kv::ToValue](https://docs.rs/log/latest/log/kv/trait.ToValue.html), or is prefixed with `%` to record it with
+/// `Display` and `?` to record it with `Debug`. A bare `error` records the error with `Display`, as the `error` field.
+/// Values are only evaluated if the event is logged. e.g.
+/// ```rust,ignore
+/// let bytes = fs::read(&path).warn_context_kv("Could not read the cover", fields! { error, path = ?path, attempt = 3 });
+/// ```
+#[macro_export]
+macro_rules! fields {
+    ($($fields:tt)*) => {
+        $crate::Fields(
+            |__level: $crate::Level,
+             __context: &dyn ::core::fmt::Display,
+             __error: ::core::option::Option<&dyn ::core::fmt::Display>| {
+                $crate::__fields!(@normalize __level __context __error [] [] $($fields)*)
+            },
+        )
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fields {
+    // Normalizes the fields to `(key [sigil] value)`, then emits them to the single backend the event is routed to
+    (@normalize $level:ident $context:ident $error:ident [$($with_error:tt)?] [$($out:tt)*]) => {{
+        if $crate::__route_to_tracing() {
+            $crate::__fields_tracing!($level $context $error [$($with_error)?] [$($out)*]);
+        } else if $crate::__route_to_log() {
+            $crate::__fields_log!($level $context $error [$($with_error)?] [$($out)*]);
+        }
+        $crate::__fields_stub!($error [$($out)*]);
+    }};
+    (@normalize $level:ident $context:ident $error:ident [$($with_error:tt)?] [$($out:tt)*] error $(, $($rest:tt)*)?) => {
+        $crate::__fields!(@normalize $level $context $error [error] [$($out)*] $($($rest)*)?)
+    };
+    (@normalize $level:ident $context:ident $error:ident [$($with_error:tt)?] [$($out:tt)*] $key:ident = % $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__fields!(@normalize $level $context $error [$($with_error)?] [$($out)* ($key [%] $value)] $($($rest)*)?)
+    };
+    (@normalize $level:ident $context:ident $error:ident [$($with_error:tt)?] [$($out:tt)*] $key:ident = ? $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__fields!(@normalize $level $context $error [$($with_error)?] [$($out)* ($key [?] $value)] $($($rest)*)?)
+    };
+    (@normalize $level:ident $context:ident $error:ident [$($with_error:tt)?] [$($out:tt)*] $key:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__fields!(@normalize $level $context $error [$($with_error)?] [$($out)* ($key [] $value)] $($($rest)*)?)
+    };
+}
+
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+pub use tracing as __tracing;
+
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fields_tracing {
+    ($level:ident $context:ident $error:ident [error] [$($fields:tt)*]) => {
+        match $error {
+            ::core::option::Option::Some($error) => {
+                $crate::__fields_tracing!(@event $level $context [(error [%] $error) $($fields)*])
+            }
+            ::core::option::Option::None => {
+                $crate::__fields_tracing!(@event $level $context [$($fields)*])
+            }
+        }
+    };
+    ($level:ident $context:ident $error:ident [] [$($fields:tt)*]) => {
+        $crate::__fields_tracing!(@event $level $context [$($fields)*])
+    };
+    // The level of a `tracing` event must be a constant
+    (@event $level:ident $context:ident [$(($key:ident [$($sigil:tt)?] $value:expr))*]) => {
+        match $level {
+            $crate::Level::Error => $crate::__tracing::error!($($key = $($sigil)? $value,)* "{}", $context),
+            $crate::Level::Warn => $crate::__tracing::warn!($($key = $($sigil)? $value,)* "{}", $context),
+            $crate::Level::Info => $crate::__tracing::info!($($key = $($sigil)? $value,)* "{}", $context),
+            $crate::Level::Debug => $crate::__tracing::debug!($($key = $($sigil)? $value,)* "{}", $context),
+            $crate::Level::Trace => $crate::__tracing::trace!($($key = $($sigil)? $value,)* "{}", $context),
+        }
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fields_tracing {
+    ($($args:tt)*) => {};
+}
+
+#[cfg(feature = "log")]
+#[doc(hidden)]
+pub use log as __log;
+
+#[cfg(feature = "log")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fields_log {
+    ($level:ident $context:ident $error:ident [error] [$($fields:tt)*]) => {
+        match $error {
+            ::core::option::Option::Some($error) => {
+                $crate::__fields_log!(@log $level $context [(error [%] $error) $($fields)*])
+            }
+            ::core::option::Option::None => {
+                $crate::__fields_log!(@log $level $context [$($fields)*])
+            }
+        }
+    };
+    ($level:ident $context:ident $error:ident [] [$($fields:tt)*]) => {
+        $crate::__fields_log!(@log $level $context [$($fields)*])
+    };
+    (@log $level:ident $context:ident []) => {
+        $crate::__log::log!($crate::__log_level($level), "{}", $context)
+    };
+    (@log $level:ident $context:ident [$(($key:ident [$($sigil:tt)?] $value:expr))+]) => {
+        $crate::__log::log!(
+            $crate::__log_level($level),
+            $($key $(:$sigil)? = $value),+;
+            "{}", $context
+        )
+    };
+}
+
+#[cfg(feature = "log")]
+#[doc(hidden)]
+#[inline]
+pub fn __log_level(level: Level) -> log::Level {
+    match level {
+        Level::Error => log::Level::Error,
+        Level::Warn => log::Level::Warn,
+        Level::Info => log::Level::Info,
+        Level::Debug => log::Level::Debug,
+        Level::Trace => log::Level::Trace,
+    }
+}
+
+#[cfg(not(feature = "log"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fields_log {
+    ($($args:tt)*) => {};
+}
+
+/// Without an implementation, the fields are still type checked, but never evaluated since the closure is not called.
+#[cfg(not(any(feature = "tracing", feature = "log")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fields_stub {
+    ($error:ident [$(($key:ident [$($sigil:tt)?] $value:expr))*]) => {
+        let _ = ($error, $(&$value,)*);
+    };
+}
+
+#[cfg(any(feature = "tracing", feature = "log"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fields_stub {
+    ($($args:tt)*) => {};
+}
@@ -1,11 +1,80 @@
-#![cfg_attr(not(any(test, feature = "tracing", feature = "log")), no_std)]
+#![cfg_attr(
+    not(any(test, feature = "tracing", feature = "log", feature = "throttle")),
+    no_std
+)]
 
-#[cfg(any(feature = "tracing", feature = "log", feature = "stub"))]
-mod tracing_log_stub;
-#[cfg(any(feature = "tracing", feature = "log", feature = "stub"))]
-pub use  tracing_log_stub::*;
+// The backends are additive. The `defmt` traits have the same methods as the others, so `tracing`, `log` and `sink`
+// take precedence over `defmt`, which takes precedence over `stub`.
 
-#[cfg(feature = "defmt")]
+/// Gates items on a backend that takes `Display`, i.e. any backend but `defmt` on its own.
+macro_rules! cfg_display_backend {
+    ($($item:item)*) => {
+        $(
+            #[cfg(any(
+                feature = "tracing",
+                feature = "log",
+                feature = "sink",
+                all(feature = "stub", not(feature = "defmt"))
+            ))]
+            $item
+        )*
+    };
+}
+
+cfg_display_backend! {
+    #[macro_use]
+    mod backend;
+    pub use backend::*;
+    mod tracing_log_stub;
+    pub use tracing_log_stub::*;
+    mod iter;
+    pub use iter::*;
+    mod kv;
+    pub use kv::*;
+
+    #[cfg(feature = "futures")]
+    mod futures;
+    #[cfg(feature = "futures")]
+    pub use futures::*;
+
+    #[cfg(feature = "throttle")]
+    mod throttle;
+    #[cfg(feature = "throttle")]
+    pub use throttle::*;
+}
+
+#[cfg(feature = "tracing")]
+mod span;
+#[cfg(feature = "tracing")]
+pub use span::*;
+
+#[cfg(feature = "sink")]
+mod sink;
+#[cfg(feature = "sink")]
+pub use sink::*;
+
+#[cfg(feature = "instrument")]
+pub use err_trail_impl::instrument_err;
+#[cfg(feature = "instrument")]
+#[doc(hidden)]
+pub use err_trail_impl::instrument_err_error_set as __instrument_err_error_set;
+#[cfg(feature = "instrument")]
+#[doc(hidden)]
+#[path = "instrument.rs"]
+pub mod __instrument;
+
+mod chain;
+pub use chain::*;
+mod level;
+pub use level::*;
+
+#[cfg(all(
+    feature = "defmt",
+    not(any(feature = "tracing", feature = "log", feature = "sink"))
+))]
 mod defmt;
-#[cfg(feature = "defmt")]
-pub use defmt::*;
\ No newline at end of file
+#[cfg(all(
+    feature = "defmt",
+    not(any(feature = "tracing", feature = "log", feature = "sink"))
+))]
+pub use defmt::*;
@@ -0,0 +1,91 @@
+use core::cell::UnsafeCell;
+use core::fmt::{self, Display};
+use core::panic::Location;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use crate::Level;
+
+/// A custom backend for the events of [ErrContext](crate::ErrContext), [NoneContext](crate::NoneContext), and the
+/// other context traits, e.g. an RTT channel, a ring buffer, or a test harness. Registered with [set_sink]. A set sink
+/// receives every event, instead of `tracing` or `log`.
+pub trait Sink: Sync {
+    /// Logs an event. [error] is set when the error is recorded apart from the message, e.g. by the
+    /// `*_context_kv` and `*_context_chain` methods. [location] is the call site of the context method.
+    fn log(
+        &self,
+        level: Level,
+        message: &dyn Display,
+        error: Option<&dyn Display>,
+        location: &'static Location<'static>,
+    );
+}
+
+const UNSET: u8 = 0;
+const SETTING: u8 = 1;
+const SET: u8 = 2;
+
+static STATE: AtomicU8 = AtomicU8::new(UNSET);
+static SINK: SinkCell = SinkCell(UnsafeCell::new(None));
+
+struct SinkCell(UnsafeCell<Option<&'static dyn Sink>>);
+
+// Written once, before `STATE` is `SET`, and only read after.
+unsafe impl Sync for SinkCell {}
+
+/// Registers the global [Sink]. Can only be set once. Only available on targets with atomic compare and swap, see
+/// [set_sink_racy] for the others, e.g. `thumbv6m-none-eabi`.
+#[cfg(target_has_atomic = "8")]
+pub fn set_sink(sink: &'static dyn Sink) -> Result<(), SetSinkError> {
+    match STATE.compare_exchange(UNSET, SETTING, Ordering::Acquire, Ordering::Relaxed) {
+        Ok(_) => {
+            unsafe {
+                *SINK.0.get() = Some(sink);
+            }
+            STATE.store(SET, Ordering::Release);
+            Ok(())
+        }
+        Err(_) => Err(SetSinkError(())),
+    }
+}
+
+/// Registers the global [Sink], like [set_sink], but without atomic compare and swap, so it is available on every
+/// target.
+///
+/// # Safety
+///
+/// Must not be called concurrently with itself or [set_sink], e.g. only from the start of `main` with interrupts
+/// disabled. Racing with [sink], and so with logging, is fine.
+pub unsafe fn set_sink_racy(sink: &'static dyn Sink) -> Result<(), SetSinkError> {
+    match STATE.load(Ordering::Acquire) {
+        UNSET => {
+            unsafe {
+                *SINK.0.get() = Some(sink);
+            }
+            STATE.store(SET, Ordering::Release);
+            Ok(())
+        }
+        _ => Err(SetSinkError(())),
+    }
+}
+
+/// The [Sink] registered with [set_sink].
+#[inline]
+pub fn sink() -> Option<&'static dyn Sink> {
+    if STATE.load(Ordering::Acquire) == SET {
+        unsafe { *SINK.0.get() }
+    } else {
+        None
+    }
+}
+
+/// Returned by [set_sink] and [set_sink_racy] when a [Sink] is already set.
+#[derive(Debug)]
+pub struct SetSinkError(());
+
+impl Display for SetSinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a sink has already been set")
+    }
+}
+
+impl core::error::Error for SetSinkError {}
@@ -0,0 +1,113 @@
+use core::error::Error;
+use core::fmt::Display;
+
+use tracing::field;
+use tracing::Span;
+
+use crate::DisplayChain;
+
+mod sealed {
+    /// A sealed trait to prevent external implementations.
+    pub trait Sealed {}
+}
+
+/// For marking the current [Span] failed when [Err] is encountered, with the OpenTelemetry semantic fields
+/// `otel.status_code`, `exception.message`, `exception.type` and `exception.chain`. A span only records the fields
+/// it was created with, e.g. `info_span!("upload", otel.status_code = field::Empty, exception.message = field::Empty)`.
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+pub trait ErrContextSpan<T, E: Error>: sealed::Sealed {
+    /// If [Err], recording the error on the current span and logging the chain of the error as an "error".
+    fn record_err(self) -> Result<T, E>;
+
+    /// If [Err], recording the error on the current span and logging context as an "error" with the chain of the
+    /// error.
+    fn error_context_in_span(self, context: impl Display) -> Result<T, E>;
+    /// If [Err], recording the error on the current span and logging context as a "warn" with the chain of the
+    /// error.
+    fn warn_context_in_span(self, context: impl Display) -> Result<T, E>;
+    /// If [Err], recording the error on the current span and logging context as an "info" with the chain of the
+    /// error.
+    fn info_context_in_span(self, context: impl Display) -> Result<T, E>;
+    /// If [Err], recording the error on the current span and logging context as a "debug" with the chain of the
+    /// error.
+    fn debug_context_in_span(self, context: impl Display) -> Result<T, E>;
+    /// If [Err], recording the error on the current span and logging context as a "trace" with the chain of the
+    /// error.
+    fn trace_context_in_span(self, context: impl Display) -> Result<T, E>;
+}
+
+/// Records [error] on the current span.
+fn record<E: Error>(error: &E) {
+    let span = Span::current();
+    span.record("otel.status_code", "ERROR");
+    span.record("exception.message", field::display(error));
+    span.record("exception.type", core::any::type_name::<E>());
+    span.record(
+        "exception.chain",
+        field::display(DisplayChain::new(error).inline()),
+    );
+}
+
+impl<T, E> sealed::Sealed for Result<T, E> {}
+
+impl<T, E: Error> ErrContextSpan<T, E> for Result<T, E> {
+    #[inline]
+    #[track_caller]
+    fn record_err(self) -> Result<T, E> {
+        if let Err(err) = &self {
+            record(err);
+            route!(error, error = DisplayChain::new(err));
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn error_context_in_span(self, context: impl Display) -> Result<T, E> {
+        if let Err(err) = &self {
+            record(err);
+            route!(error, context, error = DisplayChain::new(err));
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn warn_context_in_span(self, context: impl Display) -> Result<T, E> {
+        if let Err(err) = &self {
+            record(err);
+            route!(warn, context, error = DisplayChain::new(err));
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn info_context_in_span(self, context: impl Display) -> Result<T, E> {
+        if let Err(err) = &self {
+            record(err);
+            route!(info, context, error = DisplayChain::new(err));
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn debug_context_in_span(self, context: impl Display) -> Result<T, E> {
+        if let Err(err) = &self {
+            record(err);
+            route!(debug, context, error = DisplayChain::new(err));
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn trace_context_in_span(self, context: impl Display) -> Result<T, E> {
+        if let Err(err) = &self {
+            record(err);
+            route!(trace, context, error = DisplayChain::new(err));
+        }
+        self
+    }
+}
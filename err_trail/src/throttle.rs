@@ -0,0 +1,324 @@
+#![allow(unused_variables)]
+
+use core::fmt::{self, Display};
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+mod sealed {
+    /// A sealed trait to prevent external implementations.
+    pub trait Sealed {}
+}
+
+/// Limits how often an event is logged. The first event is logged, then the ones within [period](Throttle::new) of
+/// it are suppressed and counted. The next logged event reports the count, e.g.
+/// `Could not poll the sensor (suppressed 4312 similar errors since the last report)`. Lock-free, so it can be shared
+/// by threads. Usually created per call site with [throttle!](crate::throttle).
+pub struct Throttle {
+    period: u64,
+    last: AtomicU64,
+    suppressed: AtomicU64,
+}
+
+impl Throttle {
+    /// Logs at most one event per [period].
+    pub const fn new(period: Duration) -> Self {
+        let period = period.as_nanos();
+        Self {
+            period: if period > u64::MAX as u128 {
+                u64::MAX
+            } else {
+                period as u64
+            },
+            last: AtomicU64::new(0),
+            suppressed: AtomicU64::new(0),
+        }
+    }
+
+    /// Logs only the first event.
+    pub const fn once() -> Self {
+        Self::new(Duration::MAX)
+    }
+
+    /// Returns the number of events suppressed since the last permitted one if this event is permitted, otherwise
+    /// counts it as suppressed.
+    pub fn permit(&self) -> Option<u64> {
+        let now = now();
+        let last = self.last.load(Ordering::Acquire);
+        if last != 0 && now.saturating_sub(last) < self.period {
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        match self
+            .last
+            .compare_exchange(last, now, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => Some(self.suppressed.swap(0, Ordering::AcqRel)),
+            Err(_) => {
+                self.suppressed.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// The period events are throttled over.
+    pub fn period(&self) -> Duration {
+        Duration::from_nanos(self.period)
+    }
+}
+
+/// Nanoseconds since the first throttled event, starting at 1 so that 0 means never.
+fn now() -> u64 {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    EPOCH.get_or_init(Instant::now).elapsed().as_nanos() as u64 + 1
+}
+
+/// Creates a [Throttle] for the call site, e.g. `throttle!(Duration::from_secs(10))`, or `throttle!(once)` to only
+/// log the first event from the call site, like `log_once`.
+#[macro_export]
+macro_rules! throttle {
+    (once) => {{
+        static THROTTLE: $crate::Throttle = $crate::Throttle::once();
+        &THROTTLE
+    }};
+    ($period:expr) => {{
+        static THROTTLE: $crate::Throttle = $crate::Throttle::new($period);
+        &THROTTLE
+    }};
+}
+
+/// The context of a permitted event, with the number of events suppressed before it.
+#[cfg_attr(
+    not(any(feature = "tracing", feature = "log", feature = "sink")),
+    allow(dead_code)
+)]
+struct Summary<'a, D> {
+    context: &'a D,
+    suppressed: u64,
+}
+
+impl<D: Display> Display for Summary<'_, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.suppressed {
+            0 => write!(f, "{}", self.context),
+            1 => write!(
+                f,
+                "{} (suppressed 1 similar error since the last report)",
+                self.context
+            ),
+            suppressed => write!(
+                f,
+                "{} (suppressed {} similar errors since the last report)",
+                self.context, suppressed
+            ),
+        }
+    }
+}
+
+/// For logging a [Result] when [Err] is encountered, at most as often as a [Throttle] permits.
+#[cfg_attr(docsrs, doc(cfg(feature = "throttle")))]
+pub trait ErrContextThrottled<T, E>: sealed::Sealed {
+    /// If [Err] and [throttle] permits, logging context as an "error".
+    fn error_context_throttled(self, throttle: &Throttle, context: impl Display) -> Result<T, E>;
+    /// If [Err] and [throttle] permits, logging context as a "warn".
+    fn warn_context_throttled(self, throttle: &Throttle, context: impl Display) -> Result<T, E>;
+    /// If [Err] and [throttle] permits, logging context as an "info".
+    fn info_context_throttled(self, throttle: &Throttle, context: impl Display) -> Result<T, E>;
+    /// If [Err] and [throttle] permits, logging context as a "debug".
+    fn debug_context_throttled(self, throttle: &Throttle, context: impl Display) -> Result<T, E>;
+    /// If [Err] and [throttle] permits, logging context as a "trace".
+    fn trace_context_throttled(self, throttle: &Throttle, context: impl Display) -> Result<T, E>;
+}
+
+/// For logging an [Option] when [None] is encountered, at most as often as a [Throttle] permits.
+#[cfg_attr(docsrs, doc(cfg(feature = "throttle")))]
+pub trait NoneContextThrottled<T>: sealed::Sealed {
+    /// If [None] and [throttle] permits, logging context as an "error".
+    fn error_context_throttled(self, throttle: &Throttle, context: impl Display) -> Option<T>;
+    /// If [None] and [throttle] permits, logging context as a "warn".
+    fn warn_context_throttled(self, throttle: &Throttle, context: impl Display) -> Option<T>;
+    /// If [None] and [throttle] permits, logging context as an "info".
+    fn info_context_throttled(self, throttle: &Throttle, context: impl Display) -> Option<T>;
+    /// If [None] and [throttle] permits, logging context as a "debug".
+    fn debug_context_throttled(self, throttle: &Throttle, context: impl Display) -> Option<T>;
+    /// If [None] and [throttle] permits, logging context as a "trace".
+    fn trace_context_throttled(self, throttle: &Throttle, context: impl Display) -> Option<T>;
+}
+
+impl<T, E> sealed::Sealed for Result<T, E> {}
+
+impl<T, E> ErrContextThrottled<T, E> for Result<T, E> {
+    #[inline]
+    #[track_caller]
+    fn error_context_throttled(self, throttle: &Throttle, context: impl Display) -> Result<T, E> {
+        if self.is_err() {
+            if let Some(suppressed) = throttle.permit() {
+                route!(
+                    error,
+                    Summary {
+                        context: &context,
+                        suppressed
+                    }
+                );
+            }
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn warn_context_throttled(self, throttle: &Throttle, context: impl Display) -> Result<T, E> {
+        if self.is_err() {
+            if let Some(suppressed) = throttle.permit() {
+                route!(
+                    warn,
+                    Summary {
+                        context: &context,
+                        suppressed
+                    }
+                );
+            }
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn info_context_throttled(self, throttle: &Throttle, context: impl Display) -> Result<T, E> {
+        if self.is_err() {
+            if let Some(suppressed) = throttle.permit() {
+                route!(
+                    info,
+                    Summary {
+                        context: &context,
+                        suppressed
+                    }
+                );
+            }
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn debug_context_throttled(self, throttle: &Throttle, context: impl Display) -> Result<T, E> {
+        if self.is_err() {
+            if let Some(suppressed) = throttle.permit() {
+                route!(
+                    debug,
+                    Summary {
+                        context: &context,
+                        suppressed
+                    }
+                );
+            }
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn trace_context_throttled(self, throttle: &Throttle, context: impl Display) -> Result<T, E> {
+        if self.is_err() {
+            if let Some(suppressed) = throttle.permit() {
+                route!(
+                    trace,
+                    Summary {
+                        context: &context,
+                        suppressed
+                    }
+                );
+            }
+        }
+        self
+    }
+}
+
+impl<T> sealed::Sealed for Option<T> {}
+
+impl<T> NoneContextThrottled<T> for Option<T> {
+    #[inline]
+    #[track_caller]
+    fn error_context_throttled(self, throttle: &Throttle, context: impl Display) -> Option<T> {
+        if self.is_none() {
+            if let Some(suppressed) = throttle.permit() {
+                route!(
+                    error,
+                    Summary {
+                        context: &context,
+                        suppressed
+                    }
+                );
+            }
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn warn_context_throttled(self, throttle: &Throttle, context: impl Display) -> Option<T> {
+        if self.is_none() {
+            if let Some(suppressed) = throttle.permit() {
+                route!(
+                    warn,
+                    Summary {
+                        context: &context,
+                        suppressed
+                    }
+                );
+            }
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn info_context_throttled(self, throttle: &Throttle, context: impl Display) -> Option<T> {
+        if self.is_none() {
+            if let Some(suppressed) = throttle.permit() {
+                route!(
+                    info,
+                    Summary {
+                        context: &context,
+                        suppressed
+                    }
+                );
+            }
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn debug_context_throttled(self, throttle: &Throttle, context: impl Display) -> Option<T> {
+        if self.is_none() {
+            if let Some(suppressed) = throttle.permit() {
+                route!(
+                    debug,
+                    Summary {
+                        context: &context,
+                        suppressed
+                    }
+                );
+            }
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn trace_context_throttled(self, throttle: &Throttle, context: impl Display) -> Option<T> {
+        if self.is_none() {
+            if let Some(suppressed) = throttle.permit() {
+                route!(
+                    trace,
+                    Summary {
+                        context: &context,
+                        suppressed
+                    }
+                );
+            }
+        }
+        self
+    }
+}
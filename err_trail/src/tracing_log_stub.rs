@@ -2,6 +2,8 @@
 
 use core::fmt::Display;
 
+use crate::{Fields, FieldsFn};
+
 mod sealed {
     /// A sealed trait to prevent external implementations.
     pub trait Sealed {}
@@ -10,7 +12,12 @@ mod sealed {
 /// For logging a [Result] when [Err] is encountered.
 #[cfg_attr(
     docsrs,
-    doc(cfg(any(feature = "tracing", feature = "log", feature = "stub")))
+    doc(cfg(any(
+        feature = "tracing",
+        feature = "log",
+        feature = "sink",
+        all(feature = "stub", not(feature = "defmt"))
+    )))
 )]
 pub trait ErrContext<T, E>: sealed::Sealed {
     /// If [Err], logging context as an "error".
@@ -52,7 +59,12 @@ pub trait ErrContext<T, E>: sealed::Sealed {
 /// For logging a [Result] when [Err] is encountered and [E] is [Display]
 #[cfg_attr(
     docsrs,
-    doc(cfg(any(feature = "tracing", feature = "log", feature = "stub")))
+    doc(cfg(any(
+        feature = "tracing",
+        feature = "log",
+        feature = "sink",
+        all(feature = "stub", not(feature = "defmt"))
+    )))
 )]
 pub trait ErrContextDisplay<T, E: Display>: ErrContext<T, E> + sealed::Sealed {
     /// Consumes the [Err] of a Result. If [Err], logging the display of the error as an "error".
@@ -67,12 +79,82 @@ pub trait ErrContextDisplay<T, E: Display>: ErrContext<T, E> + sealed::Sealed {
     fn consume_as_debug(self) -> Option<T>;
     /// Consumes the [Err] of a Result. If [Err], logging the display of the error as a "trace".
     fn consume_as_trace(self) -> Option<T>;
+
+    /// If [Err], logging context as an "error" with the structured [fields](crate::fields!). A bare `error` field
+    /// records the error.
+    fn error_context_kv<F: FieldsFn>(
+        self,
+        context: impl Display,
+        fields: Fields<F>,
+    ) -> Result<T, E>;
+    /// If [Err], logging context as a "warn" with the structured [fields](crate::fields!). A bare `error` field
+    /// records the error.
+    fn warn_context_kv<F: FieldsFn>(self, context: impl Display, fields: Fields<F>)
+        -> Result<T, E>;
+    /// If [Err], logging context as an "info" with the structured [fields](crate::fields!). A bare `error` field
+    /// records the error.
+    fn info_context_kv<F: FieldsFn>(self, context: impl Display, fields: Fields<F>)
+        -> Result<T, E>;
+    /// If [Err], logging context as a "debug" with the structured [fields](crate::fields!). A bare `error` field
+    /// records the error.
+    fn debug_context_kv<F: FieldsFn>(
+        self,
+        context: impl Display,
+        fields: Fields<F>,
+    ) -> Result<T, E>;
+    /// If [Err], logging context as a "trace" with the structured [fields](crate::fields!). A bare `error` field
+    /// records the error.
+    fn trace_context_kv<F: FieldsFn>(
+        self,
+        context: impl Display,
+        fields: Fields<F>,
+    ) -> Result<T, E>;
+}
+
+/// For logging a [Result] when [Err] is encountered, with the [sources](core::error::Error::source) of [E]. The
+/// chain is rendered with [DisplayChain](crate::DisplayChain). With `tracing`, it is recorded as the `error` field.
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(
+        feature = "tracing",
+        feature = "log",
+        feature = "sink",
+        all(feature = "stub", not(feature = "defmt"))
+    )))
+)]
+pub trait ErrContextChain<T, E: core::error::Error>: sealed::Sealed {
+    /// If [Err], logging context as an "error", with the error and its sources.
+    fn error_context_chain(self, context: impl Display) -> Result<T, E>;
+    /// If [Err], logging context as a "warn", with the error and its sources.
+    fn warn_context_chain(self, context: impl Display) -> Result<T, E>;
+    /// If [Err], logging context as an "info", with the error and its sources.
+    fn info_context_chain(self, context: impl Display) -> Result<T, E>;
+    /// If [Err], logging context as a "debug", with the error and its sources.
+    fn debug_context_chain(self, context: impl Display) -> Result<T, E>;
+    /// If [Err], logging context as a "trace", with the error and its sources.
+    fn trace_context_chain(self, context: impl Display) -> Result<T, E>;
+
+    /// Consumes the [Err] of a Result. If [Err], logging the error and its sources as an "error".
+    fn consume_as_error_chain(self) -> Option<T>;
+    /// Consumes the [Err] of a Result. If [Err], logging the error and its sources as a "warn".
+    fn consume_as_warn_chain(self) -> Option<T>;
+    /// Consumes the [Err] of a Result. If [Err], logging the error and its sources as an "info".
+    fn consume_as_info_chain(self) -> Option<T>;
+    /// Consumes the [Err] of a Result. If [Err], logging the error and its sources as a "debug".
+    fn consume_as_debug_chain(self) -> Option<T>;
+    /// Consumes the [Err] of a Result. If [Err], logging the error and its sources as a "trace".
+    fn consume_as_trace_chain(self) -> Option<T>;
 }
 
 /// For logging a [Option] when [None] is encountered.
 #[cfg_attr(
     docsrs,
-    doc(cfg(any(feature = "tracing", feature = "log", feature = "stub")))
+    doc(cfg(any(
+        feature = "tracing",
+        feature = "log",
+        feature = "sink",
+        all(feature = "stub", not(feature = "defmt"))
+    )))
 )]
 pub trait NoneContext<T>: sealed::Sealed {
     /// If [None], logging context as an "error".
@@ -96,186 +178,167 @@ pub trait NoneContext<T>: sealed::Sealed {
     fn with_debug_context<F: FnOnce() -> D, D: Display>(self, f: F) -> Option<T>;
     /// Consumes the [Option]. If [None], lazily logging the result of [f] as a "trace".
     fn with_trace_context<F: FnOnce() -> D, D: Display>(self, f: F) -> Option<T>;
+
+    /// If [None], logging context as an "error" with the structured [fields](crate::fields!).
+    fn error_context_kv<F: FieldsFn>(self, context: impl Display, fields: Fields<F>) -> Option<T>;
+    /// If [None], logging context as a "warn" with the structured [fields](crate::fields!).
+    fn warn_context_kv<F: FieldsFn>(self, context: impl Display, fields: Fields<F>) -> Option<T>;
+    /// If [None], logging context as an "info" with the structured [fields](crate::fields!).
+    fn info_context_kv<F: FieldsFn>(self, context: impl Display, fields: Fields<F>) -> Option<T>;
+    /// If [None], logging context as a "debug" with the structured [fields](crate::fields!).
+    fn debug_context_kv<F: FieldsFn>(self, context: impl Display, fields: Fields<F>) -> Option<T>;
+    /// If [None], logging context as a "trace" with the structured [fields](crate::fields!).
+    fn trace_context_kv<F: FieldsFn>(self, context: impl Display, fields: Fields<F>) -> Option<T>;
 }
 
 impl<T, E> sealed::Sealed for Result<T, E> {}
 
 impl<T, E> ErrContext<T, E> for Result<T, E> {
     #[inline]
+    #[track_caller]
     fn error_context(self, context: impl Display) -> Result<T, E> {
         if self.is_err() {
-            #[cfg(feature = "tracing")]
-            tracing::error!("{}", context);
-            #[cfg(feature = "log")]
-            log::error!("{}", context);
+            route!(error, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn warn_context(self, context: impl Display) -> Result<T, E> {
         if self.is_err() {
-            #[cfg(feature = "tracing")]
-            tracing::warn!("{}", context);
-            #[cfg(feature = "log")]
-            log::warn!("{}", context);
+            route!(warn, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn info_context(self, context: impl Display) -> Result<T, E> {
         if self.is_err() {
-            #[cfg(feature = "tracing")]
-            tracing::info!("{}", context);
-            #[cfg(feature = "log")]
-            log::info!("{}", context);
+            route!(info, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn debug_context(self, context: impl Display) -> Result<T, E> {
         if self.is_err() {
-            #[cfg(feature = "tracing")]
-            tracing::debug!("{}", context);
-            #[cfg(feature = "log")]
-            log::debug!("{}", context);
+            route!(debug, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn trace_context(self, context: impl Display) -> Result<T, E> {
         if self.is_err() {
-            #[cfg(feature = "tracing")]
-            tracing::trace!("{}", context);
-            #[cfg(feature = "log")]
-            log::trace!("{}", context);
+            route!(trace, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_error_context<F: FnOnce(&E) -> D, D: Display>(self, f: F) -> Result<T, E> {
         if let Err(err) = &self {
-            #[cfg(feature = "tracing")]
-            tracing::error!("{}", f(&err));
-            #[cfg(feature = "log")]
-            log::error!("{}", f(&err));
+            route!(error, f(&err));
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_warn_context<F: FnOnce(&E) -> D, D: Display>(self, f: F) -> Result<T, E> {
         if let Err(err) = &self {
-            #[cfg(feature = "tracing")]
-            tracing::warn!("{}", f(&err));
-            #[cfg(feature = "log")]
-            log::warn!("{}", f(&err));
+            route!(warn, f(&err));
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_info_context<F: FnOnce(&E) -> D, D: Display>(self, f: F) -> Result<T, E> {
         if let Err(err) = &self {
-            #[cfg(feature = "tracing")]
-            tracing::info!("{}", f(&err));
-            #[cfg(feature = "log")]
-            log::info!("{}", f(&err));
+            route!(info, f(&err));
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_debug_context<F: FnOnce(&E) -> D, D: Display>(self, f: F) -> Result<T, E> {
         if let Err(err) = &self {
-            #[cfg(feature = "tracing")]
-            tracing::debug!("{}", f(&err));
-            #[cfg(feature = "log")]
-            log::debug!("{}", f(&err));
+            route!(debug, f(&err));
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_trace_context<F: FnOnce(&E) -> D, D: Display>(self, f: F) -> Result<T, E> {
         if let Err(err) = &self {
-            #[cfg(feature = "tracing")]
-            tracing::trace!("{}", f(&err));
-            #[cfg(feature = "log")]
-            log::trace!("{}", f(&err));
+            route!(trace, f(&err));
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn consume_with_error<F: FnOnce(&E) -> D, D: Display>(self, f: F) -> Option<T> {
         match self {
             Ok(value) => Some(value),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::error!("{}", f(&err));
-                #[cfg(feature = "log")]
-                log::error!("{}", f(&err));
+                route!(error, f(&err));
                 None
             }
         }
     }
 
     #[inline]
+    #[track_caller]
     fn consume_with_warn<F: FnOnce(&E) -> D, D: Display>(self, f: F) -> Option<T> {
         match self {
             Ok(value) => Some(value),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::warn!("{}", f(&err));
-                #[cfg(feature = "log")]
-                log::warn!("{}", f(&err));
+                route!(warn, f(&err));
                 None
             }
         }
     }
 
     #[inline]
+    #[track_caller]
     fn consume_with_info<F: FnOnce(&E) -> D, D: Display>(self, f: F) -> Option<T> {
         match self {
             Ok(value) => Some(value),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::info!("{}", f(&err));
-                #[cfg(feature = "log")]
-                log::info!("{}", f(&err));
+                route!(info, f(&err));
                 None
             }
         }
     }
 
     #[inline]
+    #[track_caller]
     fn consume_with_debug<F: FnOnce(&E) -> D, D: Display>(self, f: F) -> Option<T> {
         match self {
             Ok(value) => Some(value),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::debug!("{}", f(&err));
-                #[cfg(feature = "log")]
-                log::debug!("{}", f(&err));
+                route!(debug, f(&err));
                 None
             }
         }
     }
 
     #[inline]
+    #[track_caller]
     fn consume_with_trace<F: FnOnce(&E) -> D, D: Display>(self, f: F) -> Option<T> {
         match self {
             Ok(value) => Some(value),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::trace!("{}", f(&err));
-                #[cfg(feature = "log")]
-                log::trace!("{}", f(&err));
+                route!(trace, f(&err));
                 None
             }
         }
@@ -284,185 +347,357 @@ impl<T, E> ErrContext<T, E> for Result<T, E> {
 
 impl<T, E: Display> ErrContextDisplay<T, E> for Result<T, E> {
     #[inline]
+    #[track_caller]
     fn consume_as_error(self) -> Option<T> {
         match self {
             Ok(value) => Some(value),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::error!("{}", err);
-                #[cfg(feature = "log")]
-                log::error!("{}", err);
+                route!(error, err);
                 None
             }
         }
     }
 
     #[inline]
+    #[track_caller]
     fn consume_as_warn(self) -> Option<T> {
         match self {
             Ok(value) => Some(value),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::warn!("{}", err);
-                #[cfg(feature = "log")]
-                log::warn!("{}", err);
+                route!(warn, err);
                 None
             }
         }
     }
 
     #[inline]
+    #[track_caller]
     fn consume_as_info(self) -> Option<T> {
         match self {
             Ok(value) => Some(value),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::info!("{}", err);
-                #[cfg(feature = "log")]
-                log::info!("{}", err);
+                route!(info, err);
                 None
             }
         }
     }
 
     #[inline]
+    #[track_caller]
     fn consume_as_debug(self) -> Option<T> {
         match self {
             Ok(value) => Some(value),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::debug!("{}", err);
-                #[cfg(feature = "log")]
-                log::debug!("{}", err);
+                route!(debug, err);
                 None
             }
         }
     }
 
     #[inline]
+    #[track_caller]
     fn consume_as_trace(self) -> Option<T> {
         match self {
             Ok(value) => Some(value),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::trace!("{}", err);
-                #[cfg(feature = "log")]
-                log::trace!("{}", err);
+                route!(trace, err);
                 None
             }
         }
     }
+
+    #[inline]
+    #[track_caller]
+    fn error_context_kv<F: FieldsFn>(
+        self,
+        context: impl Display,
+        fields: Fields<F>,
+    ) -> Result<T, E> {
+        if let Err(err) = &self {
+            route!(error, context, fields = fields, Some(err));
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn warn_context_kv<F: FieldsFn>(
+        self,
+        context: impl Display,
+        fields: Fields<F>,
+    ) -> Result<T, E> {
+        if let Err(err) = &self {
+            route!(warn, context, fields = fields, Some(err));
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn info_context_kv<F: FieldsFn>(
+        self,
+        context: impl Display,
+        fields: Fields<F>,
+    ) -> Result<T, E> {
+        if let Err(err) = &self {
+            route!(info, context, fields = fields, Some(err));
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn debug_context_kv<F: FieldsFn>(
+        self,
+        context: impl Display,
+        fields: Fields<F>,
+    ) -> Result<T, E> {
+        if let Err(err) = &self {
+            route!(debug, context, fields = fields, Some(err));
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn trace_context_kv<F: FieldsFn>(
+        self,
+        context: impl Display,
+        fields: Fields<F>,
+    ) -> Result<T, E> {
+        if let Err(err) = &self {
+            route!(trace, context, fields = fields, Some(err));
+        }
+        self
+    }
+}
+
+impl<T, E: core::error::Error> ErrContextChain<T, E> for Result<T, E> {
+    #[inline]
+    #[track_caller]
+    fn error_context_chain(self, context: impl Display) -> Result<T, E> {
+        if let Err(err) = &self {
+            route!(error, context, error = crate::DisplayChain::new(err));
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn warn_context_chain(self, context: impl Display) -> Result<T, E> {
+        if let Err(err) = &self {
+            route!(warn, context, error = crate::DisplayChain::new(err));
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn info_context_chain(self, context: impl Display) -> Result<T, E> {
+        if let Err(err) = &self {
+            route!(info, context, error = crate::DisplayChain::new(err));
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn debug_context_chain(self, context: impl Display) -> Result<T, E> {
+        if let Err(err) = &self {
+            route!(debug, context, error = crate::DisplayChain::new(err));
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn trace_context_chain(self, context: impl Display) -> Result<T, E> {
+        if let Err(err) = &self {
+            route!(trace, context, error = crate::DisplayChain::new(err));
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn consume_as_error_chain(self) -> Option<T> {
+        if let Err(err) = &self {
+            route!(error, error = crate::DisplayChain::new(err));
+        }
+        self.ok()
+    }
+
+    #[inline]
+    #[track_caller]
+    fn consume_as_warn_chain(self) -> Option<T> {
+        if let Err(err) = &self {
+            route!(warn, error = crate::DisplayChain::new(err));
+        }
+        self.ok()
+    }
+
+    #[inline]
+    #[track_caller]
+    fn consume_as_info_chain(self) -> Option<T> {
+        if let Err(err) = &self {
+            route!(info, error = crate::DisplayChain::new(err));
+        }
+        self.ok()
+    }
+
+    #[inline]
+    #[track_caller]
+    fn consume_as_debug_chain(self) -> Option<T> {
+        if let Err(err) = &self {
+            route!(debug, error = crate::DisplayChain::new(err));
+        }
+        self.ok()
+    }
+
+    #[inline]
+    #[track_caller]
+    fn consume_as_trace_chain(self) -> Option<T> {
+        if let Err(err) = &self {
+            route!(trace, error = crate::DisplayChain::new(err));
+        }
+        self.ok()
+    }
 }
 
 impl<T> sealed::Sealed for Option<T> {}
 
 impl<T> NoneContext<T> for Option<T> {
     #[inline]
+    #[track_caller]
     fn error_context(self, context: impl Display) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::error!("{}", context);
-            #[cfg(feature = "log")]
-            log::error!("{}", context);
+            route!(error, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn warn_context(self, context: impl Display) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::warn!("{}", context);
-            #[cfg(feature = "log")]
-            log::warn!("{}", context);
+            route!(warn, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn info_context(self, context: impl Display) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::info!("{}", context);
-            #[cfg(feature = "log")]
-            log::info!("{}", context);
+            route!(info, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn debug_context(self, context: impl Display) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::debug!("{}", context);
-            #[cfg(feature = "log")]
-            log::debug!("{}", context);
+            route!(debug, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn trace_context(self, context: impl Display) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::trace!("{}", context);
-            #[cfg(feature = "log")]
-            log::trace!("{}", context);
+            route!(trace, context);
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_error_context<F: FnOnce() -> D, D: Display>(self, f: F) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::error!("{}", f());
-            #[cfg(feature = "log")]
-            log::error!("{}", f());
+            route!(error, f());
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_warn_context<F: FnOnce() -> D, D: Display>(self, f: F) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::warn!("{}", f());
-            #[cfg(feature = "log")]
-            log::warn!("{}", f());
+            route!(warn, f());
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_info_context<F: FnOnce() -> D, D: Display>(self, f: F) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::info!("{}", f());
-            #[cfg(feature = "log")]
-            log::info!("{}", f());
+            route!(info, f());
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_debug_context<F: FnOnce() -> D, D: Display>(self, f: F) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::debug!("{}", f());
-            #[cfg(feature = "log")]
-            log::debug!("{}", f());
+            route!(debug, f());
         }
         self
     }
 
     #[inline]
+    #[track_caller]
     fn with_trace_context<F: FnOnce() -> D, D: Display>(self, f: F) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::trace!("{}", f());
-            #[cfg(feature = "log")]
-            log::trace!("{}", f());
+            route!(trace, f());
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn error_context_kv<F: FieldsFn>(self, context: impl Display, fields: Fields<F>) -> Option<T> {
+        if self.is_none() {
+            route!(error, context, fields = fields, None);
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn warn_context_kv<F: FieldsFn>(self, context: impl Display, fields: Fields<F>) -> Option<T> {
+        if self.is_none() {
+            route!(warn, context, fields = fields, None);
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn info_context_kv<F: FieldsFn>(self, context: impl Display, fields: Fields<F>) -> Option<T> {
+        if self.is_none() {
+            route!(info, context, fields = fields, None);
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn debug_context_kv<F: FieldsFn>(self, context: impl Display, fields: Fields<F>) -> Option<T> {
+        if self.is_none() {
+            route!(debug, context, fields = fields, None);
+        }
+        self
+    }
+
+    #[inline]
+    #[track_caller]
+    fn trace_context_kv<F: FieldsFn>(self, context: impl Display, fields: Fields<F>) -> Option<T> {
+        if self.is_none() {
+            route!(trace, context, fields = fields, None);
         }
         self
     }
@@ -1,7 +1,64 @@
+#[cfg(test)]
+mod chain {
+    use err_trail::DisplayChain;
+
+    /// `could not read the cover` caused by `no such file`
+    #[derive(Debug)]
+    pub struct ReadError {
+        pub source: std::io::Error,
+    }
+
+    impl ReadError {
+        pub fn new() -> Self {
+            ReadError {
+                source: std::io::Error::new(std::io::ErrorKind::NotFound, "no such file"),
+            }
+        }
+    }
+
+    impl std::fmt::Display for ReadError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "could not read the cover")
+        }
+    }
+
+    impl std::error::Error for ReadError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.source)
+        }
+    }
+
+    #[test]
+    fn test_display_chain() {
+        let error = ReadError::new();
+        assert_eq!(
+            DisplayChain::new(&error).inline().to_string(),
+            "could not read the cover: no such file"
+        );
+        assert_eq!(
+            DisplayChain::new(&error).multiline().to_string(),
+            "could not read the cover\n  caused by: no such file"
+        );
+        let error: &dyn std::error::Error = &error;
+        assert_eq!(
+            DisplayChain::new(error).inline().to_string(),
+            "could not read the cover: no such file"
+        );
+        assert_eq!(
+            DisplayChain::new(&error.source().unwrap()).to_string(),
+            "no such file"
+        );
+    }
+}
+
 #[cfg(feature = "tracing")]
 #[cfg(test)]
 mod tracing {
-    use err_trail::{ErrContext, ErrContextDisplay, NoneContext};
+    use crate::chain::ReadError;
+    use err_trail::{
+        fields, ErrContext, ErrContextChain, ErrContextDisplay, ErrContextIter, ErrContextSpan,
+        NoneContext,
+    };
     use tracing_test::traced_test;
 
     #[traced_test]
@@ -13,6 +70,18 @@ mod tracing {
         assert!(logs_contain("An error occurred"));
     }
 
+    // The `defmt` traits would make the methods ambiguous if they were also in scope
+    #[traced_test]
+    #[test]
+    fn test_glob_import() {
+        use err_trail::*;
+
+        let result: Result<(), &str> = Err("error");
+        let _ = result.warn_context("A glob imported warning occurred");
+
+        assert!(logs_contain("A glob imported warning occurred"));
+    }
+
     #[traced_test]
     #[test]
     fn test_warn_context() {
@@ -22,6 +91,59 @@ mod tracing {
         assert!(logs_contain("A warning occurred"));
     }
 
+    #[traced_test]
+    #[test]
+    fn test_caller_location() {
+        let result: Result<(), &str> = Err("warning");
+        let line = line!() + 1;
+        let _ = result.warn_context("A located warning");
+
+        assert!(logs_contain(&format!(
+            "caller.file={:?} caller.line={line}",
+            file!()
+        )));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_log_errs_warn() {
+        let results = vec![Ok(1), Err("corrupt"), Ok(3)];
+        let logged: Vec<Result<u32, &str>> = results
+            .into_iter()
+            .log_errs_warn(|err| format!("Could not parse a row: {err}"))
+            .collect();
+
+        assert_eq!(logged, vec![Ok(1), Err("corrupt"), Ok(3)]);
+        assert!(logs_contain("Could not parse a row: corrupt"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_skip_errs_warn() {
+        let results: Vec<Result<u32, &str>> = vec![Ok(1), Err("truncated"), Ok(3)];
+        let values: Vec<u32> = results
+            .into_iter()
+            .skip_errs_warn(|err| format!("Skipped a row: {err}"))
+            .collect();
+
+        assert_eq!(values, vec![1, 3]);
+        assert!(logs_contain("Skipped a row: truncated"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_partition_logged_error() {
+        let results: Vec<Result<u32, &str>> = vec![Err("missing"), Ok(2), Err("locked")];
+        let (values, errors): (Vec<u32>, Vec<&str>) = results
+            .into_iter()
+            .partition_logged_error(|err| format!("Import failed: {err}"));
+
+        assert_eq!(values, vec![2]);
+        assert_eq!(errors, vec!["missing", "locked"]);
+        assert!(logs_contain("Import failed: missing"));
+        assert!(logs_contain("Import failed: locked"));
+    }
+
     #[traced_test]
     #[test]
     fn test_with_error_context() {
@@ -219,12 +341,130 @@ mod tracing {
 
         assert!(logs_contain("Lazy trace context"));
     }
+
+    #[traced_test]
+    #[test]
+    fn test_error_context_kv() {
+        let path = std::path::PathBuf::from("media/cover.png");
+        let result: Result<(), &str> = Err("not found");
+        let _ = result.error_context_kv(
+            "Could not read the cover",
+            fields! { error, path = ?path, attempt = 3, name = %"cover" },
+        );
+
+        assert!(logs_contain("Could not read the cover"));
+        assert!(logs_contain("error=not found"));
+        assert!(logs_contain(r#"path="media/cover.png""#));
+        assert!(logs_contain("attempt=3"));
+        assert!(logs_contain("name=cover"));
+        // Not moved into the fields
+        assert_eq!(path, std::path::PathBuf::from("media/cover.png"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_warn_context_kv() {
+        let result: Result<(), &str> = Err("timed out");
+        let _ = result.warn_context_kv("Retrying upload", fields! { attempt = 2 });
+
+        assert!(logs_contain("Retrying upload"));
+        assert!(logs_contain("attempt=2"));
+        assert!(!logs_contain("timed out"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_ok_context_kv() {
+        fn attempt() -> u32 {
+            panic!("Only evaluated if logged")
+        }
+        let result: Result<(), &str> = Ok(());
+        let _ = result.error_context_kv("Not logged", fields! { attempt = attempt() });
+
+        assert!(!logs_contain("Not logged"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_option_info_context_kv() {
+        let option: Option<()> = None;
+        let _ = option.info_context_kv("No cover", fields! { error, id = 7 });
+
+        assert!(logs_contain("No cover"));
+        assert!(logs_contain("id=7"));
+        assert!(!logs_contain("error="));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_error_context_chain() {
+        let result: Result<(), ReadError> = Err(ReadError::new());
+        let _ = result.error_context_chain("Upload failed");
+
+        assert!(logs_contain("Upload failed"));
+        assert!(logs_contain("error=could not read the cover: no such file"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_consume_as_warn_chain() {
+        let result: Result<(), ReadError> = Err(ReadError::new());
+        assert!(result.consume_as_warn_chain().is_none());
+
+        assert!(logs_contain("error=could not read the cover: no such file"));
+    }
+
+    fn upload_span() -> tracing::Span {
+        tracing::info_span!(
+            "upload",
+            otel.status_code = tracing::field::Empty,
+            exception.message = tracing::field::Empty,
+            exception.type = tracing::field::Empty,
+            exception.chain = tracing::field::Empty,
+        )
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_warn_context_in_span() {
+        let span = upload_span();
+        let _enter = span.enter();
+        let result: Result<(), ReadError> = Err(ReadError::new());
+        let _ = result.warn_context_in_span("Upload failed in span");
+
+        assert!(logs_contain("otel.status_code=\"ERROR\""));
+        assert!(logs_contain("exception.message=could not read the cover"));
+        assert!(logs_contain("exception.type=\"mod::chain::ReadError\""));
+        assert!(logs_contain(
+            "exception.chain=could not read the cover: no such file"
+        ));
+        assert!(logs_contain("Upload failed in span"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_record_err() {
+        let span = upload_span();
+        let _enter = span.enter();
+        let result: Result<(), ReadError> = Err(ReadError::new());
+        let _ = result.record_err();
+
+        assert!(logs_contain("otel.status_code=\"ERROR\""));
+        assert!(logs_contain(
+            "exception.chain=could not read the cover: no such file"
+        ));
+        assert!(logs_contain("error=could not read the cover: no such file"));
+    }
 }
 
-#[cfg(feature = "log")]
+// With `tracing` also enabled, the subscriber set by `traced_test` routes events away from `log`. See `routing`.
+#[cfg(all(feature = "log", not(feature = "tracing")))]
 #[cfg(test)]
 mod log {
-    use err_trail::{ErrContext, ErrContextDisplay, NoneContext};
+    use crate::chain::ReadError;
+    use err_trail::{
+        fields, ErrContext, ErrContextChain, ErrContextDisplay, ErrContextIter, NoneContext,
+    };
     use lazy_static::lazy_static;
     use log::{Level, Metadata, Record};
     use std::sync::{Arc, Mutex};
@@ -241,13 +481,35 @@ mod log {
         fn log(&self, record: &Record) {
             if self.enabled(record.metadata()) {
                 let mut logs = self.logs.lock().unwrap();
-                logs.push(format!("{}", record.args()));
+                let mut key_values = KeyValues(String::new());
+                record.key_values().visit(&mut key_values).unwrap();
+                logs.push(format!(
+                    "{}{} at {}:{} in {}",
+                    record.args(),
+                    key_values.0,
+                    record.file().unwrap_or_default(),
+                    record.line().unwrap_or_default(),
+                    record.target()
+                ));
             }
         }
 
         fn flush(&self) {}
     }
 
+    struct KeyValues(String);
+
+    impl<'kvs> log::kv::VisitSource<'kvs> for KeyValues {
+        fn visit_pair(
+            &mut self,
+            key: log::kv::Key<'kvs>,
+            value: log::kv::Value<'kvs>,
+        ) -> Result<(), log::kv::Error> {
+            self.0.push_str(&format!(" {key}={value}"));
+            Ok(())
+        }
+    }
+
     lazy_static! {
         static ref LOGS: Arc<Mutex<Vec<String>>> = {
             let logs = Arc::new(Mutex::new(Vec::new()));
@@ -288,6 +550,45 @@ mod log {
         assert!(logs_contain("A warning occurred"));
     }
 
+    #[test]
+    fn test_caller_location() {
+        clear_logs();
+        let result: Result<(), &str> = Err("warning");
+        let line = line!() + 1;
+        let _ = result.warn_context("A located warning");
+
+        assert!(logs_contain(&format!(
+            "A located warning at {}:{line}",
+            file!()
+        )));
+    }
+
+    #[test]
+    fn test_target() {
+        clear_logs();
+        let result: Result<(), &str> = Err("warning");
+        let line = line!() + 1;
+        let _ = result.warn_context("A targeted warning");
+
+        assert!(logs_contain(&format!(
+            "A targeted warning at {}:{line} in err_trail::tracing_log_stub",
+            file!()
+        )));
+    }
+
+    #[test]
+    fn test_skip_errs_info() {
+        clear_logs();
+        let results: Vec<Result<u32, &str>> = vec![Ok(1), Err("stale"), Ok(3)];
+        let values: Vec<u32> = results
+            .into_iter()
+            .skip_errs_info(|err| format!("Skipped a stale row: {err}"))
+            .collect();
+
+        assert_eq!(values, vec![1, 3]);
+        assert!(logs_contain("Skipped a stale row: stale"));
+    }
+
     #[test]
     fn test_with_error_context() {
         clear_logs();
@@ -485,4 +786,625 @@ mod log {
 
         assert!(logs_contain("Lazy trace context"));
     }
+    #[test]
+    fn test_error_context_kv() {
+        clear_logs();
+        let path = std::path::PathBuf::from("media/cover.png");
+        let result: Result<(), &str> = Err("not found");
+        let _ = result.error_context_kv(
+            "Could not read the cover",
+            fields! { error, path = ?path, attempt = 3, name = %"cover" },
+        );
+
+        assert!(logs_contain(
+            r#"Could not read the cover error=not found path="media/cover.png" attempt=3 name=cover"#
+        ));
+    }
+
+    #[test]
+    fn test_warn_context_kv() {
+        clear_logs();
+        let result: Result<(), &str> = Err("timed out");
+        let _ = result.warn_context_kv("Retrying upload", fields! { attempt = 2 });
+
+        assert!(logs_contain("Retrying upload attempt=2"));
+    }
+
+    #[test]
+    fn test_option_info_context_kv() {
+        clear_logs();
+        let option: Option<()> = None;
+        let _ = option.info_context_kv("No cover", fields! { error });
+
+        assert!(logs_contain("No cover"));
+    }
+
+    #[test]
+    fn test_error_context_chain() {
+        clear_logs();
+        let result: Result<(), ReadError> = Err(ReadError::new());
+        let _ = result.error_context_chain("Upload failed");
+
+        assert!(logs_contain(
+            "Upload failed: could not read the cover: no such file"
+        ));
+    }
+
+    #[test]
+    fn test_consume_as_warn_chain() {
+        clear_logs();
+        let result: Result<(), ReadError> = Err(ReadError::new());
+        assert!(result.consume_as_warn_chain().is_none());
+
+        assert!(logs_contain("could not read the cover: no such file"));
+    }
+}
+
+#[cfg(all(feature = "tracing", feature = "log"))]
+#[cfg(test)]
+mod routing {
+    use err_trail::{fields, ErrContext, ErrContextDisplay};
+    use lazy_static::lazy_static;
+    use log::{Metadata, Record};
+    use std::sync::{Arc, Mutex};
+    use tracing::subscriber::NoSubscriber;
+    use tracing_test::traced_test;
+
+    struct TestLogger {
+        logs: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl log::Log for TestLogger {
+        fn enabled(&self, _metadata: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            self.logs.lock().unwrap().push(record.args().to_string());
+        }
+
+        fn flush(&self) {}
+    }
+
+    lazy_static! {
+        static ref LOGS: Arc<Mutex<Vec<String>>> = {
+            let logs = Arc::new(Mutex::new(Vec::new()));
+            let test_logger = TestLogger { logs: logs.clone() };
+
+            log::set_boxed_logger(Box::new(test_logger)).unwrap();
+            log::set_max_level(log::LevelFilter::Trace);
+
+            logs
+        };
+    }
+
+    fn log_contains(expected: &str) -> bool {
+        let logs = LOGS.lock().unwrap();
+        logs.iter().any(|log| log.contains(expected))
+    }
+
+    #[test]
+    fn without_a_subscriber_events_go_to_log() {
+        lazy_static::initialize(&LOGS);
+        tracing::subscriber::with_default(NoSubscriber::default(), || {
+            let result: Result<(), &str> = Err("error");
+            let _ = result.warn_context("Routed to log");
+            let _ = result.consume_with_error(|err| format!("Routed {err} to log"));
+            let _ = result.info_context_kv("Routed fields to log", fields! { error });
+        });
+
+        assert!(log_contains("Routed to log"));
+        assert!(log_contains("Routed error to log"));
+        assert!(log_contains("Routed fields to log"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn with_a_subscriber_events_go_to_tracing() {
+        lazy_static::initialize(&LOGS);
+        let result: Result<(), &str> = Err("error");
+        let _ = result.warn_context("Routed to tracing");
+        let _ = result.info_context_kv("Routed fields to tracing", fields! { error });
+
+        assert!(logs_contain("Routed to tracing"));
+        assert!(logs_contain("Routed fields to tracing"));
+        assert!(!log_contains("Routed to tracing"));
+        assert!(!log_contains("Routed fields to tracing"));
+    }
+}
+
+// A set sink takes precedence over `tracing` and `log`, which would fail their tests.
+#[cfg(all(feature = "sink", not(any(feature = "tracing", feature = "log"))))]
+#[cfg(test)]
+mod sink {
+    use crate::chain::ReadError;
+    use err_trail::{
+        fields, set_sink, set_sink_racy, ErrContext, ErrContextChain, ErrContextDisplay, Level,
+        NoneContext, Sink,
+    };
+    use lazy_static::lazy_static;
+    use std::fmt::Display;
+    use std::panic::Location;
+    use std::sync::Mutex;
+
+    struct TestSink {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl Sink for TestSink {
+        fn log(
+            &self,
+            level: Level,
+            message: &dyn Display,
+            error: Option<&dyn Display>,
+            location: &'static Location<'static>,
+        ) {
+            let error = error
+                .map(|error| format!(" error={error}"))
+                .unwrap_or_default();
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("{level:?} {message}{error} at {}", location.file()));
+        }
+    }
+
+    lazy_static! {
+        static ref SINK: &'static TestSink = {
+            let sink = Box::leak(Box::new(TestSink {
+                events: Mutex::new(Vec::new()),
+            }));
+            set_sink(sink).unwrap();
+            sink
+        };
+    }
+
+    fn events_contain(expected: &str) -> bool {
+        let events = SINK.events.lock().unwrap();
+        events.iter().any(|event| event.contains(expected))
+    }
+
+    #[test]
+    fn test_error_context() {
+        lazy_static::initialize(&SINK);
+        let result: Result<(), &str> = Err("error");
+        let _ = result.error_context("Sink error context");
+
+        assert!(events_contain("Error Sink error context at"));
+    }
+
+    #[test]
+    fn test_with_warn_context() {
+        lazy_static::initialize(&SINK);
+        let result: Result<(), &str> = Err("bad cover");
+        let _ = result.with_warn_context(|err| format!("Sink warn context: {err}"));
+
+        assert!(events_contain("Warn Sink warn context: bad cover at"));
+    }
+
+    #[test]
+    fn test_option_info_context() {
+        lazy_static::initialize(&SINK);
+        let option: Option<()> = None;
+        let _ = option.info_context("Sink info context");
+
+        assert!(events_contain("Info Sink info context at"));
+    }
+
+    #[test]
+    fn test_context_kv() {
+        lazy_static::initialize(&SINK);
+        let result: Result<(), &str> = Err("timeout");
+        let _ = result.debug_context_kv("Sink kv context", fields! { error, attempt = 2 });
+
+        assert!(events_contain("Debug Sink kv context error=timeout at"));
+    }
+
+    #[test]
+    fn test_context_chain() {
+        lazy_static::initialize(&SINK);
+        let result: Result<(), ReadError> = Err(ReadError::new());
+        let _ = result.trace_context_chain("Sink chain context");
+
+        assert!(events_contain(
+            "Trace Sink chain context error=could not read the cover: no such file at"
+        ));
+    }
+
+    #[test]
+    fn test_location_is_the_call_site() {
+        lazy_static::initialize(&SINK);
+        let result: Result<(), &str> = Err("error");
+        let _ = result.warn_context("Sink location");
+
+        assert!(events_contain(&format!(
+            "Warn Sink location at {}",
+            file!()
+        )));
+    }
+
+    #[test]
+    fn test_set_sink_twice() {
+        lazy_static::initialize(&SINK);
+        let sink = Box::leak(Box::new(TestSink {
+            events: Mutex::new(Vec::new()),
+        }));
+
+        assert!(set_sink(sink).is_err());
+        assert!(unsafe { set_sink_racy(sink) }.is_err());
+    }
+}
+
+#[cfg(all(feature = "futures", feature = "tracing"))]
+#[cfg(test)]
+mod futures {
+    use err_trail::{FutureErrContext, StreamErrContext};
+    use futures::executor::block_on;
+    use futures::stream::{self, StreamExt};
+    use tracing_test::traced_test;
+
+    async fn fetch(ok: bool) -> Result<u32, &'static str> {
+        if ok {
+            Ok(1)
+        } else {
+            Err("timeout")
+        }
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_future_warn_context() {
+        let result = block_on(fetch(false).warn_context("Could not fetch the manifest"));
+
+        assert_eq!(result, Err("timeout"));
+        assert!(logs_contain("Could not fetch the manifest"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_future_ok_is_not_logged() {
+        let result = block_on(fetch(true).error_context("Could not fetch the index"));
+
+        assert_eq!(result, Ok(1));
+        assert!(!logs_contain("Could not fetch the index"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_future_with_error_context() {
+        let result = block_on(
+            fetch(false).with_error_context(|err| format!("Fetching the lockfile failed: {err}")),
+        );
+
+        assert_eq!(result, Err("timeout"));
+        assert!(logs_contain("Fetching the lockfile failed: timeout"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_future_caller_location() {
+        let line = line!() + 1;
+        let _ = block_on(fetch(false).info_context("A located fetch"));
+
+        assert!(logs_contain(&format!(
+            "caller.file={:?} caller.line={line}",
+            file!()
+        )));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_stream_warn_context() {
+        let uploads = stream::iter(vec![Ok(1), Err("disk full"), Ok(3)]);
+        let results: Vec<Result<u32, &str>> =
+            block_on(uploads.warn_context("An upload failed").collect());
+
+        assert_eq!(results, vec![Ok(1), Err("disk full"), Ok(3)]);
+        assert!(logs_contain("An upload failed"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_stream_with_debug_context() {
+        let uploads = stream::iter(vec![Ok(1), Err("quota")]);
+        let results: Vec<Result<u32, &str>> = block_on(
+            uploads
+                .with_debug_context(|err| format!("Upload rejected: {err}"))
+                .collect(),
+        );
+
+        assert_eq!(results, vec![Ok(1), Err("quota")]);
+        assert!(logs_contain("Upload rejected: quota"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_stream_consume_with_warn() {
+        let uploads = stream::iter(vec![Ok(1), Err("checksum mismatch"), Ok(3), Err("reset")]);
+        let values: Vec<u32> = block_on(
+            uploads
+                .consume_with_warn(|err| format!("Skipped upload: {err}"))
+                .collect(),
+        );
+
+        assert_eq!(values, vec![1, 3]);
+        assert!(logs_contain("Skipped upload: checksum mismatch"));
+        assert!(logs_contain("Skipped upload: reset"));
+    }
+}
+
+#[cfg(all(feature = "throttle", feature = "tracing"))]
+#[cfg(test)]
+mod throttle {
+    use err_trail::{throttle, ErrContextThrottled, NoneContextThrottled, Throttle};
+    use std::thread;
+    use std::time::Duration;
+    use tracing_test::traced_test;
+
+    #[test]
+    fn test_permit() {
+        let throttle = Throttle::new(Duration::from_secs(60));
+
+        assert_eq!(throttle.permit(), Some(0));
+        assert_eq!(throttle.permit(), None);
+        assert_eq!(throttle.permit(), None);
+    }
+
+    #[test]
+    fn test_permit_after_period() {
+        let throttle = Throttle::new(Duration::from_millis(20));
+
+        assert_eq!(throttle.permit(), Some(0));
+        assert_eq!(throttle.permit(), None);
+        assert_eq!(throttle.permit(), None);
+        thread::sleep(Duration::from_millis(30));
+        assert_eq!(throttle.permit(), Some(2));
+    }
+
+    #[test]
+    fn test_permit_across_threads() {
+        let throttle = Throttle::new(Duration::from_secs(60));
+        let permitted: usize = thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| (0..100).filter(|_| throttle.permit().is_some()).count()))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .sum()
+        });
+
+        assert_eq!(permitted, 1);
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_warn_context_throttled() {
+        for _ in 0..5 {
+            let result: Result<(), &str> = Err("sensor offline");
+            let _ = result.warn_context_throttled(
+                throttle!(Duration::from_secs(60)),
+                "Could not poll the sensor",
+            );
+        }
+
+        logs_assert(|lines: &[&str]| {
+            match lines
+                .iter()
+                .filter(|line| line.contains("Could not poll the sensor"))
+                .count()
+            {
+                1 => Ok(()),
+                count => Err(format!("Expected 1 event, got {count}")),
+            }
+        });
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_summary_of_suppressed_events() {
+        static THROTTLE: Throttle = Throttle::new(Duration::from_millis(20));
+        let result: Result<(), &str> = Err("disk full");
+        for _ in 0..3 {
+            let _ = result.error_context_throttled(&THROTTLE, "Could not write the cache");
+        }
+        thread::sleep(Duration::from_millis(30));
+        let _ = result.error_context_throttled(&THROTTLE, "Could not write the cache");
+
+        assert!(logs_contain(
+            "Could not write the cache (suppressed 2 similar errors since the last report)"
+        ));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_once() {
+        for _ in 0..3 {
+            let option: Option<()> = None;
+            let _ = option.info_context_throttled(throttle!(once), "No cover art configured");
+        }
+
+        logs_assert(|lines: &[&str]| {
+            match lines
+                .iter()
+                .filter(|line| line.contains("No cover art configured"))
+                .count()
+            {
+                1 => Ok(()),
+                count => Err(format!("Expected 1 event, got {count}")),
+            }
+        });
+    }
+}
+
+#[cfg(all(feature = "instrument", feature = "tracing"))]
+#[cfg(test)]
+mod instrument {
+    use err_trail::instrument_err;
+    use futures::executor::block_on;
+    use tracing_test::traced_test;
+
+    #[derive(Debug)]
+    struct ReadError {
+        source: std::io::Error,
+    }
+
+    impl std::fmt::Display for ReadError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "could not read the manifest")
+        }
+    }
+
+    impl std::error::Error for ReadError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.source)
+        }
+    }
+
+    #[instrument_err]
+    fn read_manifest(path: &str) -> Result<String, ReadError> {
+        Err(ReadError {
+            source: std::io::Error::new(std::io::ErrorKind::NotFound, format!("{path} not found")),
+        })
+    }
+
+    #[instrument_err(level = "warn", fields(path, attempt))]
+    fn parse_manifest(path: &str, attempt: u32) -> Result<u32, String> {
+        let version: u32 = path.parse().map_err(|_| "not a number".to_owned())?;
+        Ok(version + attempt)
+    }
+
+    #[instrument_err(level = info)]
+    async fn fetch_manifest(ok: bool) -> Result<u32, &'static str> {
+        if ok {
+            return Ok(1);
+        }
+        Err("timeout")
+    }
+
+    #[instrument_err(fields(path))]
+    fn read_cover(path: String) -> Result<Vec<u8>, std::io::Error> {
+        std::fs::read(path)
+    }
+
+    #[instrument_err(fields(versions))]
+    fn latest(versions: &mut Vec<u32>) -> Result<&mut u32, &'static str> {
+        versions.last_mut().ok_or("no versions")
+    }
+
+    #[derive(Debug)]
+    struct Registry {
+        name: &'static str,
+        versions: Vec<u32>,
+    }
+
+    impl Registry {
+        #[instrument_err(fields(package))]
+        fn resolve(&self, package: &str) -> Result<&str, String> {
+            Err(format!("{package} is not in {}", self.name))
+        }
+
+        #[instrument_err(fields(self, index))]
+        fn version_mut(&mut self, index: usize) -> Result<&mut u32, String> {
+            let len = self.versions.len();
+            self.versions
+                .get_mut(index)
+                .ok_or_else(|| format!("{index} is out of {len} versions"))
+        }
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_error_chain_is_logged() {
+        assert!(read_manifest("Cargo.toml").is_err());
+        assert!(logs_contain("`read_manifest` failed"));
+        assert!(logs_contain(
+            "could not read the manifest: Cargo.toml not found"
+        ));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_level_and_fields() {
+        assert_eq!(parse_manifest("invalid", 2), Err("not a number".to_owned()));
+        assert!(logs_contain("WARN"));
+        assert!(logs_contain(
+            "`parse_manifest` failed path=\"invalid\" attempt=2"
+        ));
+        assert!(logs_contain("not a number"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_ok_is_not_logged() {
+        assert_eq!(parse_manifest("1", 2), Ok(3));
+        assert!(!logs_contain("`parse_manifest` failed"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_async_fn() {
+        assert_eq!(block_on(fetch_manifest(true)), Ok(1));
+        assert!(!logs_contain("`fetch_manifest` failed"));
+        assert_eq!(block_on(fetch_manifest(false)), Err("timeout"));
+        assert!(logs_contain("INFO"));
+        assert!(logs_contain("`fetch_manifest` failed"));
+        assert!(logs_contain("timeout"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_method() {
+        let registry = Registry {
+            name: "crates.io",
+            versions: Vec::new(),
+        };
+
+        assert!(registry.resolve("serde").is_err());
+        assert!(logs_contain("`resolve` failed package=\"serde\""));
+        assert!(logs_contain("serde is not in crates.io"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_mutable_borrow_is_returned() {
+        let mut registry = Registry {
+            name: "crates.io",
+            versions: vec![1],
+        };
+
+        *registry.version_mut(0).unwrap() = 2;
+        assert_eq!(registry.versions, vec![2]);
+        assert!(registry.version_mut(3).is_err());
+        assert!(logs_contain(
+            "`version_mut` failed self=Registry { name: \"crates.io\", versions: [2] } index=3"
+        ));
+        assert!(logs_contain("3 is out of 1 versions"));
+
+        let mut versions = vec![1];
+        *latest(&mut versions).unwrap() = 2;
+        assert_eq!(versions, vec![2]);
+        assert!(latest(&mut Vec::new()).is_err());
+        assert!(logs_contain("`latest` failed versions=[]"));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_long_fields_are_truncated() {
+        let path = "a".repeat(300);
+        assert!(read_cover(path.clone()).is_err());
+        assert!(logs_contain(&format!(
+            "`read_cover` failed path=\"{}...",
+            &path[..249]
+        )));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_moved_argument() {
+        assert!(read_cover("missing-cover.png".to_owned()).is_err());
+        assert!(logs_contain(
+            "`read_cover` failed path=\"missing-cover.png\""
+        ));
+    }
 }
@@ -0,0 +1,200 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
+use syn::{Ident, ItemFn, LitStr, ReturnType};
+
+/// Logs any `Err` returned from the function, with the function name, the captured arguments, and the error with its
+/// sources, through the backend `err_trail` is compiled with. e.g.
+/// ```rust,ignore
+/// #[instrument_err(level = "warn", fields(path))]
+/// async fn load_cover(path: &Path) -> Result<Vec<u8>, io::Error> { .. }
+/// ```
+#[proc_macro_attribute]
+pub fn instrument_err(
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    instrument(quote!(::err_trail), args, item)
+}
+
+/// [instrument_err] for the re-export by `error_set`, which does not require a dependency on `err_trail`.
+#[doc(hidden)]
+#[proc_macro_attribute]
+pub fn instrument_err_error_set(
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    instrument(quote!(::error_set), args, item)
+}
+
+fn instrument(
+    krate: TokenStream,
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut instrument_args = InstrumentArgs::default();
+    let parser = syn::meta::parser(|meta| instrument_args.parse(meta));
+    syn::parse_macro_input!(args with parser);
+    let item_fn = syn::parse_macro_input!(item as ItemFn);
+    match expand(krate, instrument_args, item_fn) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+#[derive(Default)]
+struct InstrumentArgs {
+    level: Option<Ident>,
+    fields: Vec<Ident>,
+}
+
+impl InstrumentArgs {
+    /// Parses `level = "warn"` or `level = warn`, and `fields(arg, ..)`.
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("level") {
+            let value = meta.value()?;
+            let level = if value.peek(LitStr) {
+                let level: LitStr = value.parse()?;
+                Ident::new(&level.value(), level.span())
+            } else {
+                value.parse()?
+            };
+            if !matches!(
+                level.to_string().as_str(),
+                "error" | "warn" | "info" | "debug" | "trace"
+            ) {
+                return Err(syn::Error::new(
+                    level.span(),
+                    "expected one of `error`, `warn`, `info`, `debug` or `trace`",
+                ));
+            }
+            self.level = Some(level);
+            Ok(())
+        } else if meta.path.is_ident("fields") {
+            meta.parse_nested_meta(|field| match field.path.get_ident() {
+                Some(ident) => {
+                    self.fields.push(ident.clone());
+                    Ok(())
+                }
+                None => Err(field.error("expected the name of an argument")),
+            })
+        } else {
+            Err(meta.error("expected `level` or `fields`"))
+        }
+    }
+}
+
+fn expand(krate: TokenStream, args: InstrumentArgs, item_fn: ItemFn) -> syn::Result<TokenStream> {
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = item_fn;
+    if let Some(constness) = &sig.constness {
+        return Err(syn::Error::new_spanned(
+            constness,
+            "`instrument_err` does not support `const fn`",
+        ));
+    }
+    let return_type = match &sig.output {
+        ReturnType::Type(_, return_type) => return_type,
+        ReturnType::Default => {
+            return Err(syn::Error::new_spanned(
+                &sig,
+                "`instrument_err` expects a function returning a `Result`",
+            ));
+        }
+    };
+    // The annotation infers the error type of `?` in the body. It is left out for `impl Trait`, which is not allowed
+    // in a `let`.
+    let annotation = if contains_impl(quote!(#return_type)) {
+        quote!()
+    } else {
+        quote!(: #return_type)
+    };
+    let body = if sig.asyncness.is_some() {
+        quote!(async #block.await)
+    } else {
+        quote!(#krate::__instrument::call_once(move || #block))
+    };
+
+    let function = sig.ident.to_string();
+    let level = args
+        .level
+        .unwrap_or_else(|| Ident::new("error", Span::call_site()));
+    let level = match level.to_string().as_str() {
+        "error" => quote!(#krate::Level::Error),
+        "warn" => quote!(#krate::Level::Warn),
+        "info" => quote!(#krate::Level::Info),
+        "debug" => quote!(#krate::Level::Debug),
+        _ => quote!(#krate::Level::Trace),
+    };
+    // The fields are formatted before the body runs, which may move or mutably borrow them
+    let (capture, fields) = if args.fields.is_empty() {
+        (quote!(), quote!(::core::option::Option::None))
+    } else {
+        for field in &args.fields {
+            check_is_argument(&sig, field)?;
+        }
+        let field_names = args.fields.iter().map(|field| field.to_string());
+        let fields = &args.fields;
+        (
+            quote! {
+                let __err_trail_fields = #krate::__instrument::Fields::new(
+                    &[#((#field_names, &#fields as &dyn ::core::fmt::Debug)),*]
+                );
+            },
+            quote!(::core::option::Option::Some(&__err_trail_fields)),
+        )
+    };
+    // The location is that of the function name, since `#[track_caller]` would be the caller of the function
+    let location = quote_spanned!(sig.ident.span()=> ::core::panic::Location::caller());
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            #capture
+            let __err_trail_result #annotation = #body;
+            if let ::core::result::Result::Err(__err_trail_error) = &__err_trail_result {
+                #[allow(unused_imports)]
+                use #krate::__instrument::{ViaDisplay as _, ViaError as _};
+                let __err_trail_error =
+                    (&&&#krate::__instrument::Chain(__err_trail_error)).__err_trail_display();
+                #krate::__instrument::log(
+                    #level,
+                    #function,
+                    &__err_trail_error,
+                    #fields,
+                    #location,
+                );
+            }
+            __err_trail_result
+        }
+    })
+}
+
+/// The captured field is an argument of the function.
+fn check_is_argument(sig: &syn::Signature, field: &Ident) -> syn::Result<()> {
+    let is_argument = sig.inputs.iter().any(|input| match input {
+        syn::FnArg::Receiver(_) => field == "self",
+        syn::FnArg::Typed(pat_type) => {
+            matches!(&*pat_type.pat, syn::Pat::Ident(pat_ident) if &pat_ident.ident == field)
+        }
+    });
+    if is_argument {
+        Ok(())
+    } else {
+        Err(syn::Error::new(
+            field.span(),
+            format!("`{}` is not an argument of the function", field),
+        ))
+    }
+}
+
+fn contains_impl(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => ident == "impl",
+        TokenTree::Group(group) => contains_impl(group.stream()),
+        _ => false,
+    })
+}
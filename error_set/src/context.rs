@@ -3,6 +3,22 @@
 use core::fmt::Debug;
 use core::fmt::Display;
 
+/// Emits the `tracing` or the `log` event, whichever backend `err_trail` routes events to.
+macro_rules! route {
+    ($tracing:expr, $log:expr) => {{
+        #[cfg(all(feature = "tracing", feature = "log"))]
+        if err_trail::__route_to_tracing() {
+            $tracing;
+        } else {
+            $log;
+        }
+        #[cfg(all(feature = "tracing", not(feature = "log")))]
+        $tracing;
+        #[cfg(all(feature = "log", not(feature = "tracing")))]
+        $log;
+    }};
+}
+
 mod sealed {
     pub trait Sealed {}
 }
@@ -110,10 +126,7 @@ impl<T, E> ResultContext<T, E> for Result<T, E> {
     #[inline]
     fn error(self, context: impl Display) -> Result<T, E> {
         if self.is_err() {
-            #[cfg(feature = "tracing")]
-            tracing::error!("{}", context);
-            #[cfg(feature = "log")]
-            log::error!("{}", context);
+            route!(tracing::error!("{}", context), log::error!("{}", context));
         }
         self
     }
@@ -121,10 +134,7 @@ impl<T, E> ResultContext<T, E> for Result<T, E> {
     #[inline]
     fn warn(self, context: impl Display) -> Result<T, E> {
         if self.is_err() {
-            #[cfg(feature = "tracing")]
-            tracing::warn!("{}", context);
-            #[cfg(feature = "log")]
-            log::warn!("{}", context);
+            route!(tracing::warn!("{}", context), log::warn!("{}", context));
         }
         self
     }
@@ -132,10 +142,7 @@ impl<T, E> ResultContext<T, E> for Result<T, E> {
     #[inline]
     fn info(self, context: impl Display) -> Result<T, E> {
         if self.is_err() {
-            #[cfg(feature = "tracing")]
-            tracing::info!("{}", context);
-            #[cfg(feature = "log")]
-            log::info!("{}", context);
+            route!(tracing::info!("{}", context), log::info!("{}", context));
         }
         self
     }
@@ -143,10 +150,7 @@ impl<T, E> ResultContext<T, E> for Result<T, E> {
     #[inline]
     fn debug(self, context: impl Display) -> Result<T, E> {
         if self.is_err() {
-            #[cfg(feature = "tracing")]
-            tracing::debug!("{}", context);
-            #[cfg(feature = "log")]
-            log::debug!("{}", context);
+            route!(tracing::debug!("{}", context), log::debug!("{}", context));
         }
         self
     }
@@ -154,10 +158,7 @@ impl<T, E> ResultContext<T, E> for Result<T, E> {
     #[inline]
     fn trace(self, context: impl Display) -> Result<T, E> {
         if self.is_err() {
-            #[cfg(feature = "tracing")]
-            tracing::trace!("{}", context);
-            #[cfg(feature = "log")]
-            log::trace!("{}", context);
+            route!(tracing::trace!("{}", context), log::trace!("{}", context));
         }
         self
     }
@@ -167,10 +168,7 @@ impl<T, E> ResultContext<T, E> for Result<T, E> {
     #[inline]
     fn with_error<F: FnOnce(&E) -> D, D: Display>(self, f: F) -> Result<T, E> {
         if let Err(err) = &self {
-            #[cfg(feature = "tracing")]
-            tracing::error!("{}", f(&err));
-            #[cfg(feature = "log")]
-            log::error!("{}", f(&err));
+            route!(tracing::error!("{}", f(&err)), log::error!("{}", f(&err)));
         }
         self
     }
@@ -178,10 +176,7 @@ impl<T, E> ResultContext<T, E> for Result<T, E> {
     #[inline]
     fn with_warn<F: FnOnce(&E) -> D, D: Display>(self, f: F) -> Result<T, E> {
         if let Err(err) = &self {
-            #[cfg(feature = "tracing")]
-            tracing::warn!("{}", f(&err));
-            #[cfg(feature = "log")]
-            log::warn!("{}", f(&err));
+            route!(tracing::warn!("{}", f(&err)), log::warn!("{}", f(&err)));
         }
         self
     }
@@ -189,10 +184,7 @@ impl<T, E> ResultContext<T, E> for Result<T, E> {
     #[inline]
     fn with_info<F: FnOnce(&E) -> D, D: Display>(self, f: F) -> Result<T, E> {
         if let Err(err) = &self {
-            #[cfg(feature = "tracing")]
-            tracing::info!("{}", f(&err));
-            #[cfg(feature = "log")]
-            log::info!("{}", f(&err));
+            route!(tracing::info!("{}", f(&err)), log::info!("{}", f(&err)));
         }
         self
     }
@@ -200,10 +192,7 @@ impl<T, E> ResultContext<T, E> for Result<T, E> {
     #[inline]
     fn with_debug<F: FnOnce(&E) -> D, D: Display>(self, f: F) -> Result<T, E> {
         if let Err(err) = &self {
-            #[cfg(feature = "tracing")]
-            tracing::debug!("{}", f(&err));
-            #[cfg(feature = "log")]
-            log::debug!("{}", f(&err));
+            route!(tracing::debug!("{}", f(&err)), log::debug!("{}", f(&err)));
         }
         self
     }
@@ -211,10 +200,7 @@ impl<T, E> ResultContext<T, E> for Result<T, E> {
     #[inline]
     fn with_trace<F: FnOnce(&E) -> D, D: Display>(self, f: F) -> Result<T, E> {
         if let Err(err) = &self {
-            #[cfg(feature = "tracing")]
-            tracing::trace!("{}", f(&err));
-            #[cfg(feature = "log")]
-            log::trace!("{}", f(&err));
+            route!(tracing::trace!("{}", f(&err)), log::trace!("{}", f(&err)));
         }
         self
     }
@@ -226,10 +212,7 @@ impl<T, E> ResultContext<T, E> for Result<T, E> {
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::error!("{}", f(err));
-                #[cfg(feature = "log")]
-                log::error!("{}", f(err));
+                route!(tracing::error!("{}", f(err)), log::error!("{}", f(err)));
                 None
             }
         }
@@ -240,10 +223,7 @@ impl<T, E> ResultContext<T, E> for Result<T, E> {
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::warn!("{}", f(err));
-                #[cfg(feature = "log")]
-                log::warn!("{}", f(err));
+                route!(tracing::warn!("{}", f(err)), log::warn!("{}", f(err)));
                 None
             }
         }
@@ -254,10 +234,7 @@ impl<T, E> ResultContext<T, E> for Result<T, E> {
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::info!("{}", f(err));
-                #[cfg(feature = "log")]
-                log::info!("{}", f(err));
+                route!(tracing::info!("{}", f(err)), log::info!("{}", f(err)));
                 None
             }
         }
@@ -268,10 +245,7 @@ impl<T, E> ResultContext<T, E> for Result<T, E> {
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::debug!("{}", f(err));
-                #[cfg(feature = "log")]
-                log::debug!("{}", f(err));
+                route!(tracing::debug!("{}", f(err)), log::debug!("{}", f(err)));
                 None
             }
         }
@@ -282,10 +256,7 @@ impl<T, E> ResultContext<T, E> for Result<T, E> {
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::trace!("{}", f(err));
-                #[cfg(feature = "log")]
-                log::trace!("{}", f(err));
+                route!(tracing::trace!("{}", f(err)), log::trace!("{}", f(err)));
                 None
             }
         }
@@ -302,10 +273,7 @@ where
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::error!("{:?}", err);
-                #[cfg(feature = "log")]
-                log::error!("{:?}", err);
+                route!(tracing::error!("{:?}", err), log::error!("{:?}", err));
                 None
             }
         }
@@ -316,10 +284,7 @@ where
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::warn!("{:?}", err);
-                #[cfg(feature = "log")]
-                log::warn!("{:?}", err);
+                route!(tracing::warn!("{:?}", err), log::warn!("{:?}", err));
                 None
             }
         }
@@ -330,10 +295,7 @@ where
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::info!("{:?}", err);
-                #[cfg(feature = "log")]
-                log::info!("{:?}", err);
+                route!(tracing::info!("{:?}", err), log::info!("{:?}", err));
                 None
             }
         }
@@ -344,10 +306,7 @@ where
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::debug!("{:?}", err);
-                #[cfg(feature = "log")]
-                log::debug!("{:?}", err);
+                route!(tracing::debug!("{:?}", err), log::debug!("{:?}", err));
                 None
             }
         }
@@ -358,10 +317,7 @@ where
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::trace!("{:?}", err);
-                #[cfg(feature = "log")]
-                log::trace!("{:?}", err);
+                route!(tracing::trace!("{:?}", err), log::trace!("{:?}", err));
                 None
             }
         }
@@ -378,10 +334,7 @@ where
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::error!("{}", err);
-                #[cfg(feature = "log")]
-                log::error!("{}", err);
+                route!(tracing::error!("{}", err), log::error!("{}", err));
                 None
             }
         }
@@ -392,10 +345,7 @@ where
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::warn!("{}", err);
-                #[cfg(feature = "log")]
-                log::warn!("{}", err);
+                route!(tracing::warn!("{}", err), log::warn!("{}", err));
                 None
             }
         }
@@ -406,10 +356,7 @@ where
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::info!("{}", err);
-                #[cfg(feature = "log")]
-                log::info!("{}", err);
+                route!(tracing::info!("{}", err), log::info!("{}", err));
                 None
             }
         }
@@ -420,10 +367,7 @@ where
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::debug!("{}", err);
-                #[cfg(feature = "log")]
-                log::debug!("{}", err);
+                route!(tracing::debug!("{}", err), log::debug!("{}", err));
                 None
             }
         }
@@ -434,10 +378,7 @@ where
         match self {
             Ok(ok) => Some(ok),
             Err(err) => {
-                #[cfg(feature = "tracing")]
-                tracing::trace!("{}", err);
-                #[cfg(feature = "log")]
-                log::trace!("{}", err);
+                route!(tracing::trace!("{}", err), log::trace!("{}", err));
                 None
             }
         }
@@ -452,10 +393,7 @@ impl<T> OptionContext<T> for Option<T> {
     #[inline]
     fn error(self, context: impl Display) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::error!("{}", context);
-            #[cfg(feature = "log")]
-            log::error!("{}", context);
+            route!(tracing::error!("{}", context), log::error!("{}", context));
         }
         self
     }
@@ -463,10 +401,7 @@ impl<T> OptionContext<T> for Option<T> {
     #[inline]
     fn warn(self, context: impl Display) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::warn!("{}", context);
-            #[cfg(feature = "log")]
-            log::warn!("{}", context);
+            route!(tracing::warn!("{}", context), log::warn!("{}", context));
         }
         self
     }
@@ -474,10 +409,7 @@ impl<T> OptionContext<T> for Option<T> {
     #[inline]
     fn info(self, context: impl Display) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::info!("{}", context);
-            #[cfg(feature = "log")]
-            log::info!("{}", context);
+            route!(tracing::info!("{}", context), log::info!("{}", context));
         }
         self
     }
@@ -485,10 +417,7 @@ impl<T> OptionContext<T> for Option<T> {
     #[inline]
     fn debug(self, context: impl Display) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::debug!("{}", context);
-            #[cfg(feature = "log")]
-            log::debug!("{}", context);
+            route!(tracing::debug!("{}", context), log::debug!("{}", context));
         }
         self
     }
@@ -496,10 +425,7 @@ impl<T> OptionContext<T> for Option<T> {
     #[inline]
     fn trace(self, context: impl Display) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::trace!("{}", context);
-            #[cfg(feature = "log")]
-            log::trace!("{}", context);
+            route!(tracing::trace!("{}", context), log::trace!("{}", context));
         }
         self
     }
@@ -509,10 +435,7 @@ impl<T> OptionContext<T> for Option<T> {
     #[inline]
     fn with_error<F: FnOnce() -> D, D: Display>(self, f: F) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::error!("{}", f());
-            #[cfg(feature = "log")]
-            log::error!("{}", f());
+            route!(tracing::error!("{}", f()), log::error!("{}", f()));
         }
         self
     }
@@ -520,10 +443,7 @@ impl<T> OptionContext<T> for Option<T> {
     #[inline]
     fn with_warn<F: FnOnce() -> D, D: Display>(self, f: F) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::warn!("{}", f());
-            #[cfg(feature = "log")]
-            log::warn!("{}", f());
+            route!(tracing::warn!("{}", f()), log::warn!("{}", f()));
         }
         self
     }
@@ -531,10 +451,7 @@ impl<T> OptionContext<T> for Option<T> {
     #[inline]
     fn with_info<F: FnOnce() -> D, D: Display>(self, f: F) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::info!("{}", f());
-            #[cfg(feature = "log")]
-            log::info!("{}", f());
+            route!(tracing::info!("{}", f()), log::info!("{}", f()));
         }
         self
     }
@@ -542,10 +459,7 @@ impl<T> OptionContext<T> for Option<T> {
     #[inline]
     fn with_debug<F: FnOnce() -> D, D: Display>(self, f: F) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::debug!("{}", f());
-            #[cfg(feature = "log")]
-            log::debug!("{}", f());
+            route!(tracing::debug!("{}", f()), log::debug!("{}", f()));
         }
         self
     }
@@ -553,10 +467,7 @@ impl<T> OptionContext<T> for Option<T> {
     #[inline]
     fn with_trace<F: FnOnce() -> D, D: Display>(self, f: F) -> Option<T> {
         if self.is_none() {
-            #[cfg(feature = "tracing")]
-            tracing::trace!("{}", f());
-            #[cfg(feature = "log")]
-            log::trace!("{}", f());
+            route!(tracing::trace!("{}", f()), log::trace!("{}", f()));
         }
         self
     }
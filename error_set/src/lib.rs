@@ -2,31 +2,38 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc = include_str!("../README.md")]
 
-#[cfg(any(feature = "tracing", feature = "log", feature = "context_stub"))]
+// The backends are additive. `context` and `defmt_context` declare the same traits, so `tracing` and `log` take
+// precedence over `defmt`, which takes precedence over `context_stub`.
+#[cfg(any(
+    feature = "tracing",
+    feature = "log",
+    all(feature = "context_stub", not(feature = "defmt"))
+))]
 mod context;
-#[cfg(any(feature = "tracing", feature = "log", feature = "context_stub"))]
+#[cfg(any(
+    feature = "tracing",
+    feature = "log",
+    all(feature = "context_stub", not(feature = "defmt"))
+))]
 pub use context::*;
 
-#[cfg(feature = "defmt")]
+#[cfg(all(feature = "defmt", not(any(feature = "tracing", feature = "log"))))]
 mod defmt_context;
-#[cfg(feature = "defmt")]
+#[cfg(all(feature = "defmt", not(any(feature = "tracing", feature = "log"))))]
 pub use defmt_context::*;
 
-#[cfg(all(feature = "tracing", feature = "log"))]
-compile_error!("Features 'tracing' and 'log' cannot be enabled at the same time.");
-#[cfg(all(feature = "tracing", feature = "defmt"))]
-compile_error!("Features 'tracing' and 'defmt' cannot be enabled at the same time.");
-#[cfg(all(feature = "log", feature = "defmt"))]
-compile_error!("Features 'log' and 'defmt' cannot be enabled at the same time.");
-#[cfg(all(feature = "tracing", feature = "context_stub"))]
-compile_error!("Features 'tracing' and 'context_stub' cannot be enabled at the same time.");
-#[cfg(all(feature = "log", feature = "context_stub"))]
-compile_error!("Features 'log' and 'context_stub' cannot be enabled at the same time.");
-#[cfg(all(feature = "defmt", feature = "context_stub"))]
-compile_error!("Features 'defmt' and 'context_stub' cannot be enabled at the same time.");
-
 pub use error_set_impl::*;
-#[cfg(any(feature = "tracing", feature = "log", feature = "context_stub", feature = "defmt"))]
+
+/// The expansion refers to `error_set`, rather than `err_trail`, which may not be a dependency.
+#[cfg(feature = "instrument")]
+pub use err_trail::__instrument_err_error_set as instrument_err;
+#[cfg(any(
+    feature = "tracing",
+    feature = "log",
+    feature = "context_stub",
+    feature = "defmt",
+    feature = "instrument"
+))]
 pub use err_trail::*;
 
 pub trait CoerceResult<T, E1> {
@@ -39,3 +46,40 @@ impl<T, E1> CoerceResult<T, E1> for Result<T, E1> {
         self.map_err(Into::<E2>::into)
     }
 }
+
+/// Builds an error from a source error and the fields it holds. Generated by `error_set!` as a context selector
+/// for each source struct variant, e.g. `ReadCtx { path }` for `Read(std::io::Error) { path: PathBuf }`.
+pub trait IntoError<E> {
+    /// The error the context is attached to.
+    type Source;
+
+    fn into_error(self, source: Self::Source) -> E;
+}
+
+/// Attaches the fields of a context selector to the [Err] of a [Result], e.g.
+/// `fs::read(&path).context(ReadCtx { path: &path })?`.
+pub trait ResultExt<T, E1> {
+    /// Builds the error of [context] from the [Err].
+    fn context<C: IntoError<E2, Source = E1>, E2>(self, context: C) -> Result<T, E2>;
+
+    /// Lazily call [f] if the Result is an [Err] and build the error of the returned context from it.
+    fn with_context<F: FnOnce(&E1) -> C, C: IntoError<E2, Source = E1>, E2>(
+        self,
+        f: F,
+    ) -> Result<T, E2>;
+}
+
+impl<T, E1> ResultExt<T, E1> for Result<T, E1> {
+    #[inline]
+    fn context<C: IntoError<E2, Source = E1>, E2>(self, context: C) -> Result<T, E2> {
+        self.map_err(|source| context.into_error(source))
+    }
+
+    #[inline]
+    fn with_context<F: FnOnce(&E1) -> C, C: IntoError<E2, Source = E1>, E2>(
+        self,
+        f: F,
+    ) -> Result<T, E2> {
+        self.map_err(|source| f(&source).into_error(source))
+    }
+}
@@ -75,6 +75,653 @@ pub mod only_empty_set {
     }
 }
 
+#[cfg(test)]
+pub mod uninhabited_set {
+    use core::convert::Infallible;
+
+    use error_set::error_set;
+
+    error_set! {
+        NoError = {};
+        ParseError = {
+            InvalidDigit,
+        };
+        GenericError<T: core::fmt::Debug + core::fmt::Display> = {
+            Value {
+                value: T,
+            },
+        };
+    }
+
+    fn never() -> Result<u8, NoError> {
+        Ok(1)
+    }
+
+    fn infallible() -> Result<u8, Infallible> {
+        Ok(2)
+    }
+
+    fn parse() -> Result<u8, ParseError> {
+        let one = never()?;
+        let two = infallible()?;
+        Ok(one + two)
+    }
+
+    fn generic() -> Result<u8, GenericError<u8>> {
+        Ok(infallible()?)
+    }
+
+    #[test]
+    fn test() {
+        assert_eq!(parse().unwrap(), 3);
+        assert_eq!(generic().unwrap(), 2);
+        let no_error: Result<u8, NoError> = infallible().map_err(Into::into);
+        assert_eq!(no_error.unwrap(), 2);
+    }
+}
+
+#[cfg(test)]
+pub mod header_options {
+    mod errors {
+        use error_set::error_set;
+
+        error_set! {
+            #![error_set(vis = pub(crate), derive(Clone, PartialEq), result_alias, non_exhaustive)]
+            MediaError = {
+                NotFound,
+                Corrupt {
+                    offset: u64,
+                },
+            } || DecodeError;
+            DecodeError = {
+                InvalidHeader,
+            };
+            #[error_set(derive(), result_alias = false, non_exhaustive = false)]
+            Io = {
+                IoError(std::io::Error),
+            };
+        }
+    }
+
+    use errors::*;
+
+    fn decode() -> DecodeResult<()> {
+        Err(DecodeError::InvalidHeader)
+    }
+
+    fn load() -> MediaResult<u8> {
+        decode()?;
+        Ok(1)
+    }
+
+    #[test]
+    fn test() {
+        let error = load().unwrap_err();
+        assert_eq!(error.clone(), MediaError::InvalidHeader);
+        assert_ne!(error, MediaError::NotFound);
+        let media = MediaError::Corrupt { offset: 1 };
+        assert_eq!(media.clone(), media);
+        let io = Io::IoError(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+        // Not `non_exhaustive`
+        match io {
+            Io::IoError(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod set_display {
+    use error_set::error_set;
+
+    error_set! {
+        #[display("media error: {}")]
+        MediaError = {
+            NotFound,
+            #[display("corrupt at {offset}")]
+            Corrupt {
+                offset: u64,
+            },
+        } || DecodeError;
+        #[display("decode error: {}")]
+        DecodeError = {
+            #[display("invalid header")]
+            InvalidHeader,
+        };
+        #[display(with = render_upload)]
+        UploadError = {
+            TooLarge {
+                size: usize,
+            },
+        };
+    }
+
+    fn render_upload(error: &UploadError, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match error {
+            UploadError::TooLarge { size } => write!(f, "upload of {size} bytes is too large"),
+        }
+    }
+
+    #[test]
+    fn test() {
+        assert_eq!(
+            MediaError::NotFound.to_string(),
+            "media error: MediaError::NotFound"
+        );
+        assert_eq!(
+            MediaError::Corrupt { offset: 4 }.to_string(),
+            "media error: corrupt at 4"
+        );
+        assert_eq!(
+            DecodeError::InvalidHeader.to_string(),
+            "decode error: invalid header"
+        );
+        // The template of the superset applies to copied variants
+        let media: MediaError = DecodeError::InvalidHeader.into();
+        assert_eq!(media.to_string(), "media error: invalid header");
+        assert_eq!(
+            UploadError::TooLarge { size: 10 }.to_string(),
+            "upload of 10 bytes is too large"
+        );
+    }
+}
+
+#[cfg(test)]
+pub mod doc_display {
+    use error_set::error_set;
+
+    error_set! {
+        #[error_set(doc_display)]
+        AuthError = {
+            /// User `{name}` does not exist.
+            /// Check the spelling.
+            ///
+            /// Not part of the message.
+            UserDoesNotExist {
+                name: String,
+            },
+            /// The provided credentials are invalid
+            InvalidCredentials,
+            #[display("Io failed")]
+            /// Ignored, since there is a `#[display(..)]`
+            IoError(std::io::Error),
+            Expired,
+        };
+        /// Variants copied from a set without `doc_display` keep the default message
+        LoginError = AuthError;
+    }
+
+    #[test]
+    fn test() {
+        assert_eq!(
+            AuthError::UserDoesNotExist {
+                name: "john".to_string()
+            }
+            .to_string(),
+            "User `john` does not exist. Check the spelling."
+        );
+        assert_eq!(
+            AuthError::InvalidCredentials.to_string(),
+            "The provided credentials are invalid"
+        );
+        let io = std::io::Error::new(std::io::ErrorKind::Other, "oops");
+        assert_eq!(AuthError::IoError(io).to_string(), "Io failed");
+        assert_eq!(AuthError::Expired.to_string(), "AuthError::Expired");
+        assert_eq!(
+            LoginError::InvalidCredentials.to_string(),
+            "LoginError::InvalidCredentials"
+        );
+    }
+}
+
+#[cfg(test)]
+pub mod display_format {
+    use error_set::error_set;
+
+    error_set! {
+        FormatError = {
+            #[display("{{literal}}")]
+            Escaped,
+            #[display(raw = "{not formatted}")]
+            Raw,
+            #[display("value: {value:>5}|")]
+            Aligned {
+                value: u32,
+            },
+            #[display("{} of {}")]
+            Positional {
+                index: u32,
+                total: u32,
+            },
+            #[display("{1}/{0}")]
+            Reordered {
+                index: u32,
+                total: u32,
+            },
+            #[display("{value:.precision$}")]
+            Precision {
+                value: f64,
+                precision: usize,
+            },
+            #[display("io: {}")]
+            IoError(std::io::Error),
+            #[display("{source} at {line}")]
+            ParseError(std::num::ParseIntError) {
+                line: u32,
+            },
+        };
+    }
+
+    #[test]
+    fn test() {
+        assert_eq!(FormatError::Escaped.to_string(), "{literal}");
+        assert_eq!(FormatError::Raw.to_string(), "{not formatted}");
+        assert_eq!(
+            FormatError::Aligned { value: 42 }.to_string(),
+            "value:    42|"
+        );
+        assert_eq!(
+            FormatError::Positional { index: 1, total: 3 }.to_string(),
+            "1 of 3"
+        );
+        assert_eq!(
+            FormatError::Reordered { index: 1, total: 3 }.to_string(),
+            "3/1"
+        );
+        assert_eq!(
+            FormatError::Precision {
+                value: 1.23456,
+                precision: 2
+            }
+            .to_string(),
+            "1.23"
+        );
+        let io = std::io::Error::new(std::io::ErrorKind::Other, "oops");
+        assert_eq!(FormatError::IoError(io).to_string(), "io: oops");
+        let source = "x".parse::<u32>().unwrap_err();
+        let message = format!("{source} at 2");
+        assert_eq!(
+            FormatError::ParseError { source, line: 2 }.to_string(),
+            message
+        );
+    }
+}
+
+#[cfg(test)]
+pub mod redact {
+    use error_set::error_set;
+
+    error_set! {
+        AuthError = {
+            #[display("Authentication failed for `{user}` with token `{token}`")]
+            AuthenticationFailed {
+                user: String,
+                #[redact]
+                token: String,
+            },
+            #[display("Invalid key {key:?}")]
+            InvalidKey {
+                #[redact(with = last_four)]
+                key: String,
+            },
+            IoError(std::io::Error),
+        };
+        LoginError = {
+            Timeout,
+        } || AuthError;
+        GenericError<T: core::fmt::Debug + core::fmt::Display> = {
+            Value {
+                #[redact]
+                value: T,
+            },
+        };
+    }
+
+    fn last_four(key: &String, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "***{}", &key[key.len() - 4..])
+    }
+
+    #[test]
+    fn test() {
+        let auth = AuthError::AuthenticationFailed {
+            user: "john".to_string(),
+            token: "secret".to_string(),
+        };
+        assert_eq!(
+            auth.to_string(),
+            "Authentication failed for `john` with token `<redacted>`"
+        );
+        assert_eq!(
+            format!("{auth:?}"),
+            r#"AuthenticationFailed { user: "john", token: <redacted> }"#
+        );
+        let key = AuthError::InvalidKey {
+            key: "abcdefgh".to_string(),
+        };
+        assert_eq!(key.to_string(), "Invalid key ***efgh");
+        assert_eq!(format!("{key:?}"), "InvalidKey { key: ***efgh }");
+        let io = AuthError::IoError(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+        assert!(format!("{io:?}").starts_with("IoError(Custom"));
+
+        // Survives aggregation
+        let login: LoginError = auth.into();
+        assert_eq!(
+            format!("{login:?}"),
+            r#"AuthenticationFailed { user: "john", token: <redacted> }"#
+        );
+        assert_eq!(format!("{:?}", LoginError::Timeout), "Timeout");
+
+        let generic = GenericError::Value { value: 1 };
+        assert_eq!(format!("{generic:?}"), "Value { value: <redacted> }");
+    }
+}
+
+#[cfg(test)]
+pub mod constructors {
+    use error_set::error_set;
+
+    error_set! {
+        MediaError = {
+            MissingField {
+                field: String,
+            },
+            HTTPError {
+                status: u16,
+                retries: u32 = 3,
+            },
+            IoError(std::io::Error) {
+                path: std::path::PathBuf,
+            },
+            Type {
+                name: String,
+            },
+            Timeout,
+        };
+        #[disable(Constructors)]
+        UploadError = {
+            MissingField {
+                field: String,
+            },
+        };
+        GenericError<T: core::fmt::Debug> = {
+            Value {
+                value: T,
+            },
+        };
+    }
+
+    impl UploadError {
+        pub fn missing_field(field: &str) -> Self {
+            UploadError::MissingField {
+                field: format!("upload.{field}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test() {
+        let missing = MediaError::missing_field("author");
+        assert!(matches!(missing, MediaError::MissingField { ref field } if field == "author"));
+
+        let http = MediaError::http_error(200u8);
+        assert!(matches!(
+            http,
+            MediaError::HTTPError {
+                status: 200,
+                retries: 3
+            }
+        ));
+
+        let io = MediaError::io_error(
+            std::io::Error::new(std::io::ErrorKind::NotFound, "oops"),
+            "media/cover.png",
+        );
+        assert!(
+            matches!(io, MediaError::IoError { ref path, .. } if path.as_os_str() == "media/cover.png")
+        );
+
+        let r#type = MediaError::r#type("video");
+        assert!(matches!(r#type, MediaError::Type { ref name } if name == "video"));
+
+        let upload = UploadError::missing_field("author");
+        assert!(
+            matches!(upload, UploadError::MissingField { ref field } if field == "upload.author")
+        );
+
+        let generic = GenericError::value(1);
+        assert!(matches!(generic, GenericError::Value { value: 1 }));
+    }
+}
+
+#[cfg(test)]
+pub mod context_selectors {
+    use std::path::{Path, PathBuf};
+
+    use error_set::{error_set, ResultExt};
+
+    error_set! {
+        MediaError = {
+            Read(std::io::Error) {
+                path: PathBuf,
+                attempts: u32 = 1,
+            },
+            Parse(std::num::ParseIntError) {
+                line: usize,
+                content: String,
+            },
+        };
+        UploadError = {
+            Timeout,
+        } || MediaError;
+        GenericError<T: core::fmt::Debug> = {
+            Invalid(std::fmt::Error) {
+                value: T,
+            },
+        };
+    }
+
+    fn read(path: &Path) -> Result<Vec<u8>, MediaError> {
+        let bytes = std::fs::read(path).context(ReadCtx { path })?;
+        Ok(bytes)
+    }
+
+    fn upload(path: &Path) -> Result<Vec<u8>, UploadError> {
+        let bytes = std::fs::read(path).context(ReadCtx { path })?;
+        Ok(bytes)
+    }
+
+    #[test]
+    fn test() {
+        let media = read(Path::new("does/not/exist")).unwrap_err();
+        assert!(matches!(
+            media,
+            MediaError::Read { ref path, attempts: 1, .. } if path == Path::new("does/not/exist")
+        ));
+
+        // Coerces into supersets with `?`
+        let upload = upload(Path::new("does/not/exist")).unwrap_err();
+        assert!(matches!(
+            upload,
+            UploadError::Read { ref path, .. } if path == Path::new("does/not/exist")
+        ));
+
+        let parse: Result<u32, MediaError> = "abc".parse::<u32>().with_context(|_| ParseCtx {
+            line: 3usize,
+            content: "abc",
+        });
+        assert!(matches!(
+            parse,
+            Err(MediaError::Parse { line: 3, ref content, .. }) if content == "abc"
+        ));
+
+        let generic: Result<(), GenericError<i32>> =
+            Err(std::fmt::Error).context(InvalidCtx { value: 1 });
+        assert!(matches!(
+            generic,
+            Err(GenericError::Invalid { value: 1, .. })
+        ));
+    }
+}
+
+#[cfg(test)]
+pub mod shared_context_selectors {
+    use std::path::{Path, PathBuf};
+
+    use error_set::{error_set, ResultExt};
+
+    error_set! {
+        CoverError = {
+            Read(std::io::Error) {
+                path: PathBuf,
+            },
+        };
+        ManifestError = {
+            Read(std::io::Error) {
+                path: PathBuf,
+                attempts: u32 = 3,
+            },
+        };
+    }
+
+    #[test]
+    fn test() {
+        let path = Path::new("does/not/exist");
+        let cover: Result<Vec<u8>, CoverError> = std::fs::read(path).context(ReadCtx { path });
+        assert!(matches!(cover, Err(CoverError::Read { .. })));
+
+        let manifest: Result<Vec<u8>, ManifestError> =
+            std::fs::read(path).context(ReadCtx { path });
+        assert!(matches!(
+            manifest,
+            Err(ManifestError::Read { attempts: 3, .. })
+        ));
+    }
+}
+
+#[cfg(test)]
+pub mod disabled_context_selectors {
+    use std::path::PathBuf;
+
+    use error_set::error_set;
+
+    error_set! {
+        #[disable(ContextSelectors)]
+        MediaError = {
+            Read(std::io::Error) {
+                path: PathBuf,
+            },
+        };
+    }
+
+    /// Would collide with the context selector of `MediaError::Read`
+    #[allow(dead_code)]
+    struct ReadCtx;
+
+    #[test]
+    fn test() {
+        let media = std::fs::read("does/not/exist").map_err(|source| MediaError::Read {
+            source,
+            path: PathBuf::from("does/not/exist"),
+        });
+        assert!(matches!(media, Err(MediaError::Read { .. })));
+    }
+}
+
+#[cfg(test)]
+pub mod io_errors {
+    use std::io::{self, ErrorKind, Read};
+
+    use error_set::error_set;
+
+    error_set! {
+        #[into_io]
+        MediaError = {
+            #[io_kind(NotFound)]
+            MissingCover,
+            #[io_kind(InvalidData)]
+            BadHeader {
+                offset: usize,
+            },
+            #[from_io(kind = NotFound)]
+            NotFound(std::io::Error),
+            #[from_io(kind = PermissionDenied | AlreadyExists)]
+            Denied(std::io::Error) {},
+            Io(std::io::Error),
+            Unsupported,
+        };
+        #[into_io]
+        UploadError = {
+            Timeout,
+        } || MediaError;
+        CoverError = {
+            #[from_io(kind = NotFound)]
+            Missing(io::Error),
+            Io(std::io::Error),
+        };
+    }
+
+    struct CoverReader {
+        has_cover: bool,
+    }
+
+    impl Read for CoverReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            if !self.has_cover {
+                Err(MediaError::MissingCover)?;
+            }
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn test() {
+        let error: std::io::Error = MediaError::MissingCover.into();
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+        let inner = error
+            .into_inner()
+            .unwrap()
+            .downcast::<MediaError>()
+            .unwrap();
+        assert!(matches!(*inner, MediaError::MissingCover));
+
+        let error: std::io::Error = MediaError::BadHeader { offset: 4 }.into();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        let error: std::io::Error = MediaError::Unsupported.into();
+        assert_eq!(error.kind(), ErrorKind::Other);
+        // Keeps the kind of an io source
+        let error: std::io::Error =
+            MediaError::Io(std::io::Error::from(ErrorKind::TimedOut)).into();
+        assert_eq!(error.kind(), ErrorKind::TimedOut);
+
+        let error = CoverReader { has_cover: false }
+            .read(&mut [0; 4])
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+        assert!(CoverReader { has_cover: true }.read(&mut [0; 4]).is_ok());
+
+        // Dispatches by kind
+        let media: MediaError = std::io::Error::from(ErrorKind::NotFound).into();
+        assert!(matches!(media, MediaError::NotFound(_)));
+        let media: MediaError = std::io::Error::from(ErrorKind::PermissionDenied).into();
+        assert!(matches!(media, MediaError::Denied { .. }));
+        let media: MediaError = std::io::Error::from(ErrorKind::TimedOut).into();
+        assert!(matches!(media, MediaError::Io(_)));
+
+        // Kept when aggregated
+        let upload: UploadError = std::io::Error::from(ErrorKind::NotFound).into();
+        assert!(matches!(upload, UploadError::NotFound(_)));
+        let error: std::io::Error = UploadError::MissingCover.into();
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+        let error: std::io::Error = UploadError::Timeout.into();
+        assert_eq!(error.kind(), ErrorKind::Other);
+
+        // Matches the source through an alias of the path
+        let cover: CoverError = io::Error::from(ErrorKind::NotFound).into();
+        assert!(matches!(cover, CoverError::Missing(_)));
+        let cover: CoverError = io::Error::from(ErrorKind::TimedOut).into();
+        assert!(matches!(cover, CoverError::Io(_)));
+    }
+}
+
 #[cfg(test)]
 pub mod error_sources_of_same_name {
     use error_set::error_set;
@@ -834,6 +1481,351 @@ pub mod disable {
     }
 }
 
+#[cfg(test)]
+pub mod disable_variant {
+    use error_set::error_set;
+
+    error_set! {
+        X = {
+            #[disable(From)]
+            IoError(std::io::Error),
+            OtherIoError(std::io::Error),
+        };
+        Y = {
+            IoError(std::io::Error),
+        };
+        Z = X || Y;
+    }
+
+    // Would conflict if `X::IoError` was still a conversion target
+    impl From<Y> for X {
+        fn from(y: Y) -> Self {
+            match y {
+                Y::IoError(e) => X::OtherIoError(e),
+            }
+        }
+    }
+
+    #[test]
+    fn test() {
+        let x: X = std::io::Error::new(std::io::ErrorKind::Other, "oops").into();
+        assert!(matches!(x, X::OtherIoError(_)));
+        let x: X = Y::IoError(std::io::Error::new(std::io::ErrorKind::Other, "oops")).into();
+        assert!(matches!(x, X::OtherIoError(_)));
+
+        // Not carried over to supersets
+        let z: Z = X::IoError(std::io::Error::new(std::io::ErrorKind::Other, "oops")).into();
+        assert!(matches!(z, Z::IoError(_)));
+        let z: Z = Y::IoError(std::io::Error::new(std::io::ErrorKind::Other, "oops")).into();
+        assert!(matches!(z, Z::IoError(_)));
+    }
+}
+
+#[cfg(test)]
+pub mod convert {
+    use error_set::error_set;
+
+    error_set! {
+        OldError = {
+            IoError(std::io::Error),
+            Parse {
+                line: u32,
+            },
+            Timeout,
+            Unknown,
+        };
+        #[convert(from = OldError, map(Parse => Invalid { reason: format!("parse error on line {line}") }))]
+        #[convert(from = OtherError, fallback = Internal)]
+        NewError = {
+            IoError(std::io::Error),
+            #[display("{reason}")]
+            Invalid {
+                reason: String,
+            },
+            Timeout,
+            Unknown,
+            Internal,
+        };
+        OtherError = {
+            Timeout,
+            Busy,
+            Crashed(std::fmt::Error),
+        };
+        #[convert(from = OtherError, map(Timeout => WrappedError::Timeout), fallback = Other)]
+        WrappedError = {
+            Timeout,
+            Other(OtherError),
+        };
+        #[convert(from = OtherError, fallback = Other)]
+        BoxedError = {
+            Timeout,
+            Other(Box<dyn std::error::Error>),
+        };
+        #[convert(from = OldError, map(Parse => parse_error(line)), fallback = Internal)]
+        MappedError = {
+            Invalid {
+                reason: String,
+            },
+            Internal,
+        };
+    }
+
+    fn parse_error(line: u32) -> MappedError {
+        MappedError::Invalid {
+            reason: format!("line {line}"),
+        }
+    }
+
+    #[test]
+    fn test() {
+        let new: NewError = OldError::Parse { line: 3 }.into();
+        assert_eq!(new.to_string(), "parse error on line 3");
+        let new: NewError = OldError::Timeout.into();
+        assert!(matches!(new, NewError::Timeout));
+        let new: NewError =
+            OldError::IoError(std::io::Error::new(std::io::ErrorKind::Other, "oops")).into();
+        assert!(matches!(new, NewError::IoError(_)));
+
+        let new: NewError = OtherError::Timeout.into();
+        assert!(matches!(new, NewError::Timeout));
+        let new: NewError = OtherError::Busy.into();
+        assert!(matches!(new, NewError::Internal));
+        let new: NewError = OtherError::Crashed(std::fmt::Error).into();
+        assert!(matches!(new, NewError::Internal));
+
+        let wrapped: WrappedError = OtherError::Timeout.into();
+        assert!(matches!(wrapped, WrappedError::Timeout));
+        let wrapped: WrappedError = OtherError::Busy.into();
+        assert!(matches!(wrapped, WrappedError::Other(OtherError::Busy)));
+
+        let boxed: BoxedError = OtherError::Busy.into();
+        assert!(
+            matches!(boxed, BoxedError::Other(ref other) if other.to_string() == "OtherError::Busy")
+        );
+
+        let mapped: MappedError = OldError::Parse { line: 7 }.into();
+        assert!(matches!(mapped, MappedError::Invalid { reason } if reason == "line 7"));
+        let mapped: MappedError = OldError::Timeout.into();
+        assert!(matches!(mapped, MappedError::Internal));
+    }
+}
+
+#[cfg(test)]
+pub mod wrap {
+    use std::error::Error;
+
+    use error_set::error_set;
+
+    error_set! {
+        StorageError = {
+            IoError(std::io::Error),
+            #[display("disk full")]
+            DiskFull,
+        };
+        NetworkError = {
+            Timeout,
+            FmtError(std::fmt::Error),
+        };
+        ParseError = {
+            FmtError(std::fmt::Error),
+        };
+        ServiceError = wrap(StorageError) || wrap(NetworkError) || wrap(ParseError) || {
+            Unavailable,
+        };
+    }
+
+    #[test]
+    fn test() {
+        let service: ServiceError = StorageError::DiskFull.into();
+        assert!(matches!(
+            service,
+            ServiceError::StorageError(StorageError::DiskFull)
+        ));
+        assert_eq!(service.to_string(), "disk full");
+        assert!(service
+            .source()
+            .is_some_and(|e| e.downcast_ref::<StorageError>().is_some()));
+
+        let service: ServiceError = NetworkError::Timeout.into();
+        assert!(matches!(
+            service,
+            ServiceError::NetworkError(NetworkError::Timeout)
+        ));
+        assert_eq!(service.to_string(), "NetworkError::Timeout");
+
+        // Only `StorageError` accepts `std::io::Error`
+        let service: ServiceError = std::io::Error::new(std::io::ErrorKind::Other, "oops").into();
+        assert!(matches!(
+            service,
+            ServiceError::StorageError(StorageError::IoError(_))
+        ));
+        assert_eq!(service.to_string(), "oops");
+    }
+}
+
+#[cfg(test)]
+pub mod default_fields {
+    use error_set::error_set;
+
+    error_set! {
+        ParseError = {
+            MissingField {
+                field: String,
+            },
+            Timeout,
+            IoError(std::io::Error),
+        };
+        RequestError = {
+            #[display("missing `{field}`, hint: {hint:?}")]
+            MissingField {
+                field: String,
+                hint: Option<String> = None,
+            },
+            Timeout {
+                retry_after: Option<u64> = Some(10),
+            },
+            IoError(std::io::Error) {
+                attempts: u32 = 1,
+            },
+        };
+    }
+
+    #[test]
+    fn test() {
+        let request: RequestError = ParseError::MissingField {
+            field: "author".to_string(),
+        }
+        .into();
+        assert!(matches!(
+            request,
+            RequestError::MissingField { ref field, hint: None } if field == "author"
+        ));
+        assert_eq!(request.to_string(), "missing `author`, hint: None");
+
+        let request: RequestError = ParseError::Timeout.into();
+        assert!(matches!(
+            request,
+            RequestError::Timeout {
+                retry_after: Some(10)
+            }
+        ));
+
+        let request: RequestError =
+            ParseError::IoError(std::io::Error::new(std::io::ErrorKind::Other, "oops")).into();
+        assert!(matches!(request, RequestError::IoError { attempts: 1, .. }));
+    }
+}
+
+#[cfg(test)]
+pub mod relaxed_matching {
+    use error_set::error_set;
+    use std::io;
+
+    error_set! {
+        ReadError = {
+            Invalid {
+                line: usize,
+                column: usize,
+            },
+            IoError(io::Error),
+        };
+        LoadError = {
+            Invalid {
+                column: usize,
+                line: usize,
+            },
+            IoError(std::io::Error),
+        };
+        #[strict_match]
+        StrictLoadError = {
+            Invalid {
+                column: usize,
+                line: usize,
+            },
+            IoError(std::io::Error),
+        };
+        FormatError = {
+            Parse(std::fmt::Error),
+        };
+        ParseError = {
+            Parse(std::io::Error),
+            Other,
+        };
+        WriteError = {
+            Parse(core::fmt::Error),
+        };
+    }
+
+    // Would conflict if a `From` was generated, since the sources only share the last segment
+    impl From<FormatError> for ParseError {
+        fn from(_: FormatError) -> Self {
+            ParseError::Other
+        }
+    }
+
+    // Would conflict if `#[strict_match]` still generated a `From`
+    impl From<ReadError> for StrictLoadError {
+        fn from(error: ReadError) -> Self {
+            match error {
+                ReadError::Invalid { line, column } => StrictLoadError::Invalid { column, line },
+                ReadError::IoError(source) => StrictLoadError::IoError(source),
+            }
+        }
+    }
+
+    #[test]
+    fn test() {
+        let load: LoadError = ReadError::Invalid { line: 1, column: 2 }.into();
+        assert!(matches!(load, LoadError::Invalid { column: 2, line: 1 }));
+
+        let load: LoadError =
+            ReadError::IoError(io::Error::new(io::ErrorKind::Other, "oops")).into();
+        assert!(matches!(load, LoadError::IoError(_)));
+
+        let load: StrictLoadError = ReadError::Invalid { line: 1, column: 2 }.into();
+        assert!(matches!(
+            load,
+            StrictLoadError::Invalid { column: 2, line: 1 }
+        ));
+        let parse: ParseError = FormatError::Parse(std::fmt::Error).into();
+        assert!(matches!(parse, ParseError::Other));
+
+        let write: WriteError = FormatError::Parse(std::fmt::Error).into();
+        assert!(matches!(write, WriteError::Parse(_)));
+    }
+}
+
+#[cfg(test)]
+pub mod same_name_variant_override {
+    use error_set::error_set;
+
+    error_set! {
+        ConfigError = {
+            Parse {
+                line: u32,
+            },
+            Missing,
+        };
+        JsonError = {
+            Parse(std::num::ParseIntError),
+            Missing,
+        };
+        LoadError = {
+            Parse {
+                line: u32,
+            },
+        } || ConfigError || JsonError;
+    }
+
+    #[test]
+    fn test() {
+        let load: LoadError = ConfigError::Parse { line: 3 }.into();
+        assert!(matches!(load, LoadError::Parse { line: 3 }));
+        let load: LoadError = ConfigError::Missing.into();
+        assert!(matches!(load, LoadError::Missing));
+    }
+}
+
 #[cfg(test)]
 pub mod from_for_generic_and_regular {
     use error_set::error_set;
@@ -893,6 +1885,84 @@ pub mod generics_nested {
 #[cfg(test)]
 pub mod should_not_compile_tests {
 
+    #[test]
+    fn conflicting_variant_shapes() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/conflicting_variant_shapes.rs");
+    }
+
+    #[test]
+    fn conflicting_variant_source_paths() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/conflicting_variant_source_paths.rs");
+    }
+
+    #[test]
+    fn conflicting_context_selector_fields() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/conflicting_context_selector_fields.rs");
+    }
+
+    #[test]
+    fn display_unknown_field() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/display_unknown_field.rs");
+    }
+
+    #[test]
+    fn set_display_template_placeholders() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/set_display_template_placeholders.rs");
+    }
+
+    #[test]
+    fn conflicting_constructor_names() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/conflicting_constructor_names.rs");
+    }
+
+    #[test]
+    fn from_io_without_fallback() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/from_io_without_fallback.rs");
+    }
+
+    #[test]
+    fn from_io_not_io_error() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/from_io_not_io_error.rs");
+    }
+
+    #[test]
+    fn from_io_disabled_fallback() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/from_io_disabled_fallback.rs");
+    }
+
+    #[test]
+    fn io_kind_without_into_io() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/io_kind_without_into_io.rs");
+    }
+
+    #[test]
+    fn convert_missing_variant() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/convert_missing_variant.rs");
+    }
+
+    #[test]
+    fn convert_fallback_source() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/convert_fallback_source.rs");
+    }
+
+    #[test]
+    fn convert_fallback_concrete_box() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/convert_fallback_concrete_box.rs");
+    }
+
     #[test]
     fn depends_on_self() {
         let t = trybuild::TestCases::new();
@@ -1207,3 +2277,33 @@ mod log {
         assert!(!logs_contain("This should not log an error"));
     }
 }
+
+#[cfg(all(feature = "instrument", feature = "tracing"))]
+#[cfg(test)]
+mod instrument {
+    use error_set::{error_set, instrument_err};
+    use tracing_test::traced_test;
+
+    error_set! {
+        ManifestError = {
+            #[display("The manifest `{path}` is missing")]
+            Missing { path: String },
+        };
+    }
+
+    #[instrument_err(level = "warn", fields(path))]
+    fn read_manifest(path: &str) -> Result<(), ManifestError> {
+        Err(ManifestError::Missing {
+            path: path.to_owned(),
+        })
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_instrument_err() {
+        assert!(read_manifest("Cargo.toml").is_err());
+        assert!(logs_contain("WARN"));
+        assert!(logs_contain("`read_manifest` failed path=\"Cargo.toml\""));
+        assert!(logs_contain("The manifest `Cargo.toml` is missing"));
+    }
+}
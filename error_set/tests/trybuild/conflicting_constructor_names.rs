@@ -0,0 +1,14 @@
+use error_set::error_set;
+
+error_set! {
+    MediaError = {
+        IOError {
+            path: String,
+        },
+        IoError {
+            path: String,
+        },
+    };
+}
+
+fn main() {}
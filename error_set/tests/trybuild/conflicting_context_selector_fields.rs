@@ -0,0 +1,16 @@
+use error_set::error_set;
+
+error_set! {
+    CoverError = {
+        Read(std::io::Error) {
+            path: String,
+        },
+    };
+    ManifestError = {
+        Read(std::io::Error) {
+            line: usize,
+        },
+    };
+}
+
+fn main() {}
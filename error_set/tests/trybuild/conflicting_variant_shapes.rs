@@ -0,0 +1,15 @@
+use error_set::error_set;
+
+error_set! {
+    ConfigError = {
+        Parse {
+            line: u32,
+        },
+    };
+    JsonError = {
+        Parse(std::num::ParseIntError),
+    };
+    LoadError = ConfigError || JsonError;
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use error_set::error_set;
+
+error_set! {
+    FormatError = {
+        Parse(std::fmt::Error),
+    };
+    ReadError = {
+        Parse(std::io::Error),
+    };
+    LoadError = FormatError || ReadError;
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use error_set::error_set;
+
+error_set! {
+    OldError = {
+        Timeout,
+        Busy,
+    };
+    #[convert(from = OldError, fallback = Other)]
+    NewError = {
+        Timeout,
+        Other(std::io::Error),
+    };
+}
+
+fn main() {}
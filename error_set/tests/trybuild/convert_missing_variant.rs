@@ -0,0 +1,16 @@
+use error_set::error_set;
+
+error_set! {
+    X = {
+        A,
+        B {
+            value: u32,
+        },
+    };
+    #[convert(from = X)]
+    Y = {
+        A,
+    };
+}
+
+pub fn main() {}
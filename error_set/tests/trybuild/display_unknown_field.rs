@@ -0,0 +1,12 @@
+use error_set::error_set;
+
+error_set! {
+    AuthError = {
+        #[display("User `{name}` with role `{role}` does not exist")]
+        UserDoesNotExist {
+            name: String,
+        },
+    };
+}
+
+fn main() {}
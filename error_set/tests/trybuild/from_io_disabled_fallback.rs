@@ -0,0 +1,12 @@
+use error_set::error_set;
+
+error_set! {
+    MediaError = {
+        #[from_io(kind = NotFound)]
+        NotFound(std::io::Error),
+        #[disable(From)]
+        Io(std::io::Error),
+    };
+}
+
+fn main() {}
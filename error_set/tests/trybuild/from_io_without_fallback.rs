@@ -0,0 +1,11 @@
+use error_set::error_set;
+
+error_set! {
+    MediaError = {
+        #[from_io(kind = NotFound)]
+        NotFound(std::io::Error),
+        MissingCover,
+    };
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use error_set::error_set;
+
+error_set! {
+    MediaError = {
+        #[io_kind(NotFound)]
+        MissingCover,
+    };
+}
+
+fn main() {}
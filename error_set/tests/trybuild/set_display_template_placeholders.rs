@@ -0,0 +1,12 @@
+use error_set::error_set;
+
+error_set! {
+    #[display("media error: {} at {offset}")]
+    MediaError = {
+        Corrupt {
+            offset: u64,
+        },
+    };
+}
+
+fn main() {}
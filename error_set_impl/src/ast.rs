@@ -10,6 +10,14 @@ use syn::{
 
 const DISPLAY_ATTRIBUTE_NAME: &str = "display";
 const DISABLE_ATTRIBUTE_NAME: &str = "disable";
+const CONVERT_ATTRIBUTE_NAME: &str = "convert";
+const WRAP_NAME: &str = "wrap";
+const STRICT_MATCH_ATTRIBUTE_NAME: &str = "strict_match";
+const ERROR_SET_ATTRIBUTE_NAME: &str = "error_set";
+const REDACT_ATTRIBUTE_NAME: &str = "redact";
+const IO_KIND_ATTRIBUTE_NAME: &str = "io_kind";
+const INTO_IO_ATTRIBUTE_NAME: &str = "into_io";
+const FROM_IO_ATTRIBUTE_NAME: &str = "from_io";
 
 #[derive(Clone)]
 pub(crate) struct AstErrorSet {
@@ -19,6 +27,7 @@ pub(crate) struct AstErrorSet {
 impl Parse for AstErrorSet {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut set_items = Vec::new();
+        let header = extract_header(input)?;
 
         while !input.is_empty() {
             let fork = input.fork();
@@ -32,6 +41,10 @@ impl Parse for AstErrorSet {
                     }
                 }
             };
+            let set_item = AstErrorDeclaration {
+                options: set_item.options.with_defaults(&header),
+                ..set_item
+            };
             set_items.push(set_item);
             if input.peek(token::Semi) {
                 input.parse::<token::Semi>().unwrap();
@@ -58,7 +71,16 @@ pub(crate) struct AstErrorDeclaration {
     pub(crate) attributes: Vec<Attribute>,
     pub(crate) error_name: Ident,
     pub(crate) generics: Vec<TypeParam>,
-    pub(crate) disabled: Disabled,
+    /// `None` if there is no `#[disable(..)]` on the set
+    pub(crate) disabled: Option<Disabled>,
+    pub(crate) converts: Vec<Convert>,
+    /// `#[strict_match]` on the set. Variants of the set then only match other variants when the fields are in the
+    /// same order and source types have the exact same path.
+    pub(crate) strict_match: bool,
+    /// `#[into_io]` on the set, generates a `From` of the set for `std::io::Error`
+    pub(crate) into_io: bool,
+    pub(crate) options: SetOptions,
+    pub(crate) display: Option<SetDisplay>,
     pub(crate) parts: Vec<AstInlineOrRefError>,
 }
 
@@ -66,6 +88,13 @@ impl Parse for AstErrorDeclaration {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut attributes = input.call(Attribute::parse_outer)?;
         let disabled = extract_disabled(&mut attributes)?;
+        let converts = extract_converts(&mut attributes)?;
+        let strict_match = extract_flag_attribute(&mut attributes, STRICT_MATCH_ATTRIBUTE_NAME)?;
+        let into_io = extract_flag_attribute(&mut attributes, INTO_IO_ATTRIBUTE_NAME)?;
+        let options = extract_set_options(&mut attributes)?;
+        let display = extract_display_attribute(&mut attributes)?
+            .map(|e| syn::parse2::<SetDisplay>(e.tokens))
+            .transpose()?;
         if input.is_empty() {
             return Err(syn::Error::new(
                 input.span(),
@@ -116,6 +145,11 @@ impl Parse for AstErrorDeclaration {
             error_name,
             generics,
             disabled,
+            converts,
+            strict_match,
+            into_io,
+            options,
+            display,
             parts,
         });
     }
@@ -125,6 +159,8 @@ impl Parse for AstErrorDeclaration {
 pub(crate) enum AstInlineOrRefError {
     Inline(AstInlineError),
     Ref(RefError),
+    /// e.g. `wrap(X)`. Nests the referenced set as a variant instead of flattening it.
+    Wrap(RefError),
 }
 
 impl Parse for AstInlineOrRefError {
@@ -135,6 +171,25 @@ impl Parse for AstInlineOrRefError {
                 Err(err) => Err(err),
             };
         }
+        if input.peek(Ident) && input.peek2(token::Paren) {
+            let name = input.parse::<Ident>()?;
+            if name != WRAP_NAME {
+                return Err(syn::parse::Error::new(
+                    name.span(),
+                    format!("Expected a reference to another error enum or `{WRAP_NAME}(..)`."),
+                ));
+            }
+            let content;
+            parenthesized!(content in input);
+            let ref_error = content.parse::<RefError>()?;
+            if !content.is_empty() {
+                return Err(syn::parse::Error::new(
+                    content.span(),
+                    format!("`{WRAP_NAME}` takes a single error enum."),
+                ));
+            }
+            return Ok(AstInlineOrRefError::Wrap(ref_error));
+        }
         if input.peek(Ident) {
             return match input.parse::<RefError>() {
                 Ok(ref_error) => Ok(AstInlineOrRefError::Ref(ref_error)),
@@ -156,18 +211,11 @@ pub(crate) struct AstInlineError {
 impl Parse for AstInlineError {
     fn parse(input: ParseStream) -> Result<Self> {
         let content;
-        let save_position = input.fork();
         let _brace_token = braced!(content in input);
         let error_variants = content.parse_terminated(
             |input: ParseStream| input.parse::<AstErrorVariant>(),
             token::Comma,
         )?;
-        if error_variants.is_empty() {
-            return Err(syn::parse::Error::new(
-                save_position.span(),
-                "Inline error variants cannot be empty",
-            ));
-        }
         return Ok(AstInlineError { error_variants });
     }
 }
@@ -197,6 +245,11 @@ pub(crate) struct AstErrorVariant {
     pub(crate) attributes: Vec<Attribute>,
     pub(crate) cfg_attributes: Vec<Attribute>,
     pub(crate) display: Option<DisplayAttribute>,
+    /// `#[disable(From)]` on the variant. Not carried over when the variant is aggregated into another set.
+    pub(crate) disable_from: bool,
+    /// Created from `wrap(..)`, the source type is another set in the macro.
+    pub(crate) wrapped: bool,
+    pub(crate) io: IoAttributes,
     pub(crate) name: Ident,
     // Dev Note: `Some(Vec::new())` == `{}`, `Some(Vec::new(..))` == `{..}`, `None` == ``. `{}` means inline struct if has source as well.
     pub(crate) fields: Option<Vec<AstInlineErrorVariantField>>,
@@ -210,7 +263,25 @@ impl Parse for AstErrorVariant {
         let attributes = input.call(Attribute::parse_outer)?;
         let (mut attributes, cfg_attributes) = extract_cfg(attributes);
         let display = extract_display_attribute(&mut attributes)?;
+        let disabled = extract_disabled(&mut attributes)?.unwrap_or_default();
+        let io = extract_io(&mut attributes)?;
         let name = input.parse::<Ident>()?;
+        if disabled.display
+            || disabled.debug
+            || disabled.error
+            || disabled.constructors
+            || disabled.context_selectors
+            || disabled.from.as_ref().is_some_and(|e| !e.is_empty())
+        {
+            return Err(syn::parse::Error::new(
+                name.span(),
+                format!(
+                    "Only `From` without arguments can be used with `{}` on a variant.",
+                    DISABLE_ATTRIBUTE_NAME
+                ),
+            ));
+        }
+        let disable_from = disabled.from.is_some();
         let content: syn::Result<_> = (|| {
             let content;
             parenthesized!(content in input);
@@ -236,6 +307,15 @@ impl Parse for AstErrorVariant {
                 }
             }
         }
+        if !io.from_kinds.is_empty() && source_type.is_none() {
+            return Err(syn::parse::Error::new(
+                name.span(),
+                format!(
+                    "`{}` can only be used on a variant with a source, e.g. `{}(std::io::Error)`.",
+                    FROM_IO_ATTRIBUTE_NAME, name
+                ),
+            ));
+        }
         let content: syn::Result<_> = (|| {
             let content;
             syn::braced!(content in input);
@@ -247,6 +327,9 @@ impl Parse for AstErrorVariant {
                     attributes,
                     cfg_attributes,
                     display,
+                    disable_from,
+                    wrapped: false,
+                    io,
                     name,
                     fields: None,
                     source_type,
@@ -264,6 +347,9 @@ impl Parse for AstErrorVariant {
             attributes,
             cfg_attributes,
             display,
+            disable_from,
+            wrapped: false,
+            io,
             name,
             fields,
             source_type,
@@ -332,7 +418,7 @@ impl Parse for DisableArg {
     }
 }
 
-fn extract_disabled(attributes: &mut Vec<Attribute>) -> syn::Result<Disabled> {
+fn extract_disabled(attributes: &mut Vec<Attribute>) -> syn::Result<Option<Disabled>> {
     let mut to_remove = Vec::new();
     let mut disabled = Disabled::default();
     for (i, e) in attributes.iter().enumerate() {
@@ -344,7 +430,7 @@ fn extract_disabled(attributes: &mut Vec<Attribute>) -> syn::Result<Disabled> {
     }
 
     if to_remove.is_empty() {
-        return Ok(disabled);
+        return Ok(None);
     }
     let mut index = 0;
     attributes.retain(|_| {
@@ -353,7 +439,7 @@ fn extract_disabled(attributes: &mut Vec<Attribute>) -> syn::Result<Disabled> {
         return retain;
     });
 
-    Ok(disabled)
+    Ok(Some(disabled))
 }
 
 fn extract_disabled_helper(attribute: &Attribute) -> syn::Result<Option<Disabled>> {
@@ -369,85 +455,113 @@ fn extract_disabled_helper(attribute: &Attribute) -> syn::Result<Option<Disabled
             if &*ident != DISABLE_ATTRIBUTE_NAME {
                 return Ok(None);
             }
+            parse_disabled(list.tokens.clone()).map(Some)
+        }
+    };
+}
 
-            let punc = match syn::parse::Parser::parse2(
-                &|input: ParseStream| {
-                    Punctuated::<DisableArg, token::Comma>::parse_terminated(input)
-                },
-                list.tokens.clone(),
-            ) {
-                Ok(okay) => okay,
-                Err(_) => {
+/// Parses the arguments of `disable(..)`
+fn parse_disabled(tokens: TokenStream) -> syn::Result<Disabled> {
+    let punc = match syn::parse::Parser::parse2(
+        &|input: ParseStream| Punctuated::<DisableArg, token::Comma>::parse_terminated(input),
+        tokens.clone(),
+    ) {
+        Ok(okay) => okay,
+        Err(_) => {
+            return Err(syn::parse::Error::new(
+                tokens.span(),
+                format!("Invalid syntax for `{}` attribute.", DISABLE_ATTRIBUTE_NAME),
+            ))
+        }
+    };
+    let mut from = None;
+    let mut display = false;
+    let mut debug = false;
+    let mut error = false;
+    let mut constructors = false;
+    let mut context_selectors = false;
+    for DisableArg { name, refs } in punc {
+        let ident = name.to_string();
+        match &*ident {
+            "From" => {
+                from = Some(refs);
+            }
+            "Display" => {
+                display = true;
+                if !refs.is_empty() {
                     return Err(syn::parse::Error::new(
-                        list.tokens.span(),
-                        format!("Invalid syntax for `{}` attribute.", DISABLE_ATTRIBUTE_NAME),
-                    ))
+                        name.span(),
+                        format!(
+                            "`Display` does not take any arguments for `{}` attribute.",
+                            DISABLE_ATTRIBUTE_NAME
+                        ),
+                    ));
                 }
-            };
-            let mut from = None;
-            let mut display = false;
-            let mut debug = false;
-            let mut error = false;
-            for DisableArg { name, refs } in punc {
-                let ident = name.to_string();
-                match &*ident {
-                    "From" => {
-                        from = Some(refs);
-                    }
-                    "Display" => {
-                        display = true;
-                        if !refs.is_empty() {
-                            return Err(syn::parse::Error::new(
-                                name.span(),
-                                format!(
-                                    "`Display` does not take any arguments for `{}` attribute.",
-                                    DISABLE_ATTRIBUTE_NAME
-                                ),
-                            ));
-                        }
-                    }
-                    "Debug" => {
-                        debug = true;
-                        if !refs.is_empty() {
-                            return Err(syn::parse::Error::new(
-                                name.span(),
-                                format!(
-                                    "`Debug` does not take any arguments for `{}` attribute.",
-                                    DISABLE_ATTRIBUTE_NAME
-                                ),
-                            ));
-                        }
-                    }
-                    "Error" => {
-                        error = true;
-                        if !refs.is_empty() {
-                            return Err(syn::parse::Error::new(
-                                name.span(),
-                                format!(
-                                    "`Error` does not take any arguments for `{}` attribute.",
-                                    DISABLE_ATTRIBUTE_NAME
-                                ),
-                            ));
-                        }
-                    }
-                    _ => {
-                        return Err(syn::parse::Error::new(
-                            ident.span(),
-                            format!(
-                                "`{ident}` is not a valid option for `{DISABLE_ATTRIBUTE_NAME}`"
-                            ),
-                        ))
-                    }
+            }
+            "Debug" => {
+                debug = true;
+                if !refs.is_empty() {
+                    return Err(syn::parse::Error::new(
+                        name.span(),
+                        format!(
+                            "`Debug` does not take any arguments for `{}` attribute.",
+                            DISABLE_ATTRIBUTE_NAME
+                        ),
+                    ));
                 }
             }
-            Ok(Some(Disabled {
-                from,
-                display,
-                debug,
-                error,
-            }))
+            "Error" => {
+                error = true;
+                if !refs.is_empty() {
+                    return Err(syn::parse::Error::new(
+                        name.span(),
+                        format!(
+                            "`Error` does not take any arguments for `{}` attribute.",
+                            DISABLE_ATTRIBUTE_NAME
+                        ),
+                    ));
+                }
+            }
+            "Constructors" => {
+                constructors = true;
+                if !refs.is_empty() {
+                    return Err(syn::parse::Error::new(
+                        name.span(),
+                        format!(
+                            "`Constructors` does not take any arguments for `{}` attribute.",
+                            DISABLE_ATTRIBUTE_NAME
+                        ),
+                    ));
+                }
+            }
+            "ContextSelectors" => {
+                context_selectors = true;
+                if !refs.is_empty() {
+                    return Err(syn::parse::Error::new(
+                        name.span(),
+                        format!(
+                            "`ContextSelectors` does not take any arguments for `{}` attribute.",
+                            DISABLE_ATTRIBUTE_NAME
+                        ),
+                    ));
+                }
+            }
+            _ => {
+                return Err(syn::parse::Error::new(
+                    ident.span(),
+                    format!("`{ident}` is not a valid option for `{DISABLE_ATTRIBUTE_NAME}`"),
+                ))
+            }
         }
-    };
+    }
+    Ok(Disabled {
+        from,
+        display,
+        debug,
+        error,
+        constructors,
+        context_selectors,
+    })
 }
 
 #[derive(Clone)]
@@ -457,6 +571,10 @@ pub(crate) struct Disabled {
     pub(crate) display: bool,
     pub(crate) debug: bool,
     pub(crate) error: bool,
+    /// The `snake_case` constructors of struct variants
+    pub(crate) constructors: bool,
+    /// The `{Variant}Ctx` context selectors of source struct variants
+    pub(crate) context_selectors: bool,
 }
 
 impl Disabled {
@@ -465,6 +583,8 @@ impl Disabled {
         self.display = other.display;
         self.debug = other.debug;
         self.error = other.error;
+        self.constructors = other.constructors;
+        self.context_selectors = other.context_selectors;
     }
 }
 
@@ -475,18 +595,376 @@ impl Default for Disabled {
             display: false,
             debug: false,
             error: false,
+            constructors: false,
+            context_selectors: false,
         }
     }
 }
 
 //************************************************************************//
 
+/// Options for how sets are generated. Set for all sets with `#![error_set(..)]` at the top of the macro, or for a
+/// single set with `#[error_set(..)]`, e.g. `#![error_set(vis = pub(crate), derive(Clone), result_alias, non_exhaustive)]`.
+/// `None` means not specified.
+#[derive(Clone, Default)]
+pub(crate) struct SetOptions {
+    pub(crate) vis: Option<syn::Visibility>,
+    pub(crate) derives: Option<Vec<syn::Path>>,
+    pub(crate) result_alias: Option<bool>,
+    pub(crate) non_exhaustive: Option<bool>,
+    pub(crate) doc_display: Option<bool>,
+    pub(crate) disabled: Option<Disabled>,
+}
+
+impl SetOptions {
+    /// Fills the options not specified with [defaults].
+    fn with_defaults(self, defaults: &SetOptions) -> SetOptions {
+        SetOptions {
+            vis: self.vis.or_else(|| defaults.vis.clone()),
+            derives: self.derives.or_else(|| defaults.derives.clone()),
+            result_alias: self.result_alias.or(defaults.result_alias),
+            non_exhaustive: self.non_exhaustive.or(defaults.non_exhaustive),
+            doc_display: self.doc_display.or(defaults.doc_display),
+            disabled: self.disabled.or_else(|| defaults.disabled.clone()),
+        }
+    }
+}
+
+impl Parse for SetOptions {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut options = SetOptions::default();
+        while !input.is_empty() {
+            let name = input.parse::<Ident>()?;
+            match &*name.to_string() {
+                "vis" => {
+                    input.parse::<syn::Token![=]>()?;
+                    options.vis = Some(input.parse::<syn::Visibility>()?);
+                }
+                "derive" => {
+                    let content;
+                    parenthesized!(content in input);
+                    let derives =
+                        content.parse_terminated(syn::Path::parse_mod_style, token::Comma)?;
+                    options.derives = Some(derives.into_iter().collect());
+                }
+                "result_alias" => options.result_alias = Some(parse_flag(input)?),
+                "non_exhaustive" => options.non_exhaustive = Some(parse_flag(input)?),
+                "doc_display" => options.doc_display = Some(parse_flag(input)?),
+                "disable" => {
+                    let content;
+                    parenthesized!(content in input);
+                    options.disabled = Some(parse_disabled(content.parse()?)?);
+                }
+                _ => {
+                    return Err(syn::parse::Error::new(
+                        name.span(),
+                        format!("`{name}` is not a valid option for `{ERROR_SET_ATTRIBUTE_NAME}`"),
+                    ))
+                }
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<token::Comma>()?;
+        }
+        Ok(options)
+    }
+}
+
+/// `flag` or `flag = true/false`
+fn parse_flag(input: ParseStream) -> Result<bool> {
+    if !input.peek(syn::Token![=]) {
+        return Ok(true);
+    }
+    input.parse::<syn::Token![=]>()?;
+    Ok(input.parse::<syn::LitBool>()?.value)
+}
+
+/// The `#![error_set(..)]` inner attributes at the top of the macro
+fn extract_header(input: ParseStream) -> Result<SetOptions> {
+    let mut header = SetOptions::default();
+    for attribute in input.call(Attribute::parse_inner)? {
+        if !attribute.path().is_ident(ERROR_SET_ATTRIBUTE_NAME) {
+            return Err(syn::parse::Error::new_spanned(
+                attribute,
+                format!(
+                    "Only `#![{}(..)]` is supported at the top of the macro.",
+                    ERROR_SET_ATTRIBUTE_NAME
+                ),
+            ));
+        }
+        header = attribute.parse_args::<SetOptions>()?.with_defaults(&header);
+    }
+    Ok(header)
+}
+
+/// The `#[error_set(..)]` attributes on a set
+fn extract_set_options(attributes: &mut Vec<Attribute>) -> syn::Result<SetOptions> {
+    let mut options = SetOptions::default();
+    let mut error = None;
+    attributes.retain(|attribute| {
+        if !attribute.path().is_ident(ERROR_SET_ATTRIBUTE_NAME) {
+            return true;
+        }
+        match attribute.parse_args::<SetOptions>() {
+            Ok(these_options) => options = these_options.with_defaults(&options),
+            Err(err) => {
+                error.get_or_insert(err);
+            }
+        }
+        false
+    });
+    match error {
+        Some(error) => Err(error),
+        None => Ok(options),
+    }
+}
+
+//************************************************************************//
+
+/// An explicit conversion from another set, e.g.
+/// `#[convert(from = OtherSet, map(OldVariant => NewVariant { reason: "..".into() }), fallback = Internal)]`
+#[derive(Clone)]
+pub(crate) struct Convert {
+    pub(crate) from: Ident,
+    pub(crate) maps: Vec<ConvertMap>,
+    pub(crate) fallback: Option<Ident>,
+}
+
+/// `OldVariant => expression`. The fields of `OldVariant` are in scope for the expression.
+#[derive(Clone)]
+pub(crate) struct ConvertMap {
+    pub(crate) from_variant: Ident,
+    pub(crate) expr: syn::Expr,
+}
+
+impl Parse for ConvertMap {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let from_variant = input.parse::<Ident>()?;
+        input.parse::<syn::Token![=>]>()?;
+        let expr = input.parse::<syn::Expr>()?;
+        Ok(ConvertMap { from_variant, expr })
+    }
+}
+
+impl Parse for Convert {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let span = input.span();
+        let mut from = None;
+        let mut maps = Vec::new();
+        let mut fallback = None;
+        while !input.is_empty() {
+            let name = input.parse::<Ident>()?;
+            match &*name.to_string() {
+                "from" => {
+                    input.parse::<syn::Token![=]>()?;
+                    from = Some(input.parse::<Ident>()?);
+                }
+                "map" => {
+                    let content;
+                    parenthesized!(content in input);
+                    maps.extend(content.parse_terminated(ConvertMap::parse, token::Comma)?);
+                }
+                "fallback" => {
+                    input.parse::<syn::Token![=]>()?;
+                    fallback = Some(input.parse::<Ident>()?);
+                }
+                _ => {
+                    return Err(syn::parse::Error::new(
+                        name.span(),
+                        format!("`{name}` is not a valid option for `{CONVERT_ATTRIBUTE_NAME}`"),
+                    ))
+                }
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<token::Comma>()?;
+        }
+        let Some(from) = from else {
+            return Err(syn::parse::Error::new(
+                span,
+                format!("`{CONVERT_ATTRIBUTE_NAME}` requires a `from = ..` set."),
+            ));
+        };
+        Ok(Convert {
+            from,
+            maps,
+            fallback,
+        })
+    }
+}
+
+fn extract_converts(attributes: &mut Vec<Attribute>) -> syn::Result<Vec<Convert>> {
+    let mut converts: Vec<Convert> = Vec::new();
+    let mut error = None;
+    attributes.retain(|attribute| {
+        let syn::Meta::List(list) = &attribute.meta else {
+            return true;
+        };
+        if !list.path.is_ident(CONVERT_ATTRIBUTE_NAME) {
+            return true;
+        }
+        match list.parse_args::<Convert>() {
+            Ok(convert) => {
+                if converts.iter().any(|e| e.from == convert.from) {
+                    error.get_or_insert(syn::parse::Error::new(
+                        convert.from.span(),
+                        format!(
+                            "More than one `{}` attribute found for `{}`",
+                            CONVERT_ATTRIBUTE_NAME, convert.from
+                        ),
+                    ));
+                }
+                converts.push(convert);
+            }
+            Err(err) => {
+                error.get_or_insert(err);
+            }
+        }
+        false
+    });
+    match error {
+        Some(error) => Err(error),
+        None => Ok(converts),
+    }
+}
+
+/// A path only attribute like `#[strict_match]`, that may appear at most once.
+fn extract_flag_attribute(attributes: &mut Vec<Attribute>, name: &str) -> syn::Result<bool> {
+    let mut flag = false;
+    let mut error = None;
+    attributes.retain(|attribute| {
+        if !attribute.path().is_ident(name) {
+            return true;
+        }
+        if let Err(err) = attribute.meta.require_path_only() {
+            error.get_or_insert(err);
+        } else if flag {
+            error.get_or_insert(syn::parse::Error::new(
+                attribute.span(),
+                format!("More than one `{}` attribute found", name),
+            ));
+        }
+        flag = true;
+        false
+    });
+    match error {
+        Some(error) => Err(error),
+        None => Ok(flag),
+    }
+}
+
+/// The `std::io::ErrorKind` integration of a variant.
+#[derive(Clone, Default)]
+pub(crate) struct IoAttributes {
+    /// `#[io_kind(NotFound)]`, the kind of the `std::io::Error` the variant is converted into with `#[into_io]`
+    pub(crate) kind: Option<Ident>,
+    /// `#[from_io(kind = NotFound | PermissionDenied)]`, the kinds of `std::io::Error` converted into the variant
+    pub(crate) from_kinds: Vec<Ident>,
+}
+
+/// `kind = NotFound | PermissionDenied` in `#[from_io(..)]`
+struct FromIo {
+    kinds: Vec<Ident>,
+}
+
+impl Parse for FromIo {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse::<Ident>()?;
+        if name != "kind" {
+            return Err(syn::parse::Error::new(
+                name.span(),
+                format!("`{name}` is not a valid option for `{FROM_IO_ATTRIBUTE_NAME}`. Expected `kind`."),
+            ));
+        }
+        input.parse::<syn::Token![=]>()?;
+        let kinds = Punctuated::<Ident, syn::Token![|]>::parse_separated_nonempty(input)?;
+        Ok(FromIo {
+            kinds: kinds.into_iter().collect(),
+        })
+    }
+}
+
+fn extract_io(attributes: &mut Vec<Attribute>) -> syn::Result<IoAttributes> {
+    let mut io = IoAttributes::default();
+    let mut error = None;
+    attributes.retain(|attribute| {
+        if attribute.path().is_ident(IO_KIND_ATTRIBUTE_NAME) {
+            if io.kind.is_some() {
+                error.get_or_insert(syn::parse::Error::new(
+                    attribute.span(),
+                    format!("More than one `{}` attribute found", IO_KIND_ATTRIBUTE_NAME),
+                ));
+            }
+            match attribute.parse_args::<Ident>() {
+                Ok(kind) => io.kind = Some(kind),
+                Err(err) => {
+                    error.get_or_insert(err);
+                }
+            }
+            return false;
+        }
+        if attribute.path().is_ident(FROM_IO_ATTRIBUTE_NAME) {
+            match attribute.parse_args::<FromIo>() {
+                Ok(from_io) => io.from_kinds.extend(from_io.kinds),
+                Err(err) => {
+                    error.get_or_insert(err);
+                }
+            }
+            return false;
+        }
+        true
+    });
+    match error {
+        Some(error) => Err(error),
+        None => Ok(io),
+    }
+}
+
+//************************************************************************//
+
 /// The format string to use for display
 #[derive(Clone)]
 pub(crate) struct DisplayAttribute {
     pub(crate) tokens: TokenStream,
 }
 
+/// The `#[display(..)]` of a set, which applies to every variant
+#[derive(Clone)]
+pub(crate) enum SetDisplay {
+    /// e.g. `#[display("media error: {}")]`. The `{}` is the message of the variant.
+    Template(syn::LitStr),
+    /// e.g. `#[display(with = path::to_fn)]`. Called as `fn(&Set, &mut core::fmt::Formatter) -> core::fmt::Result`.
+    With(syn::Path),
+}
+
+impl Parse for SetDisplay {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Ident) && input.peek2(syn::Token![=]) {
+            let name = input.parse::<Ident>()?;
+            if name != "with" {
+                return Err(syn::parse::Error::new(
+                    name.span(),
+                    format!("`{name}` is not a valid option for `{DISPLAY_ATTRIBUTE_NAME}` on a set. Expected `with`."),
+                ));
+            }
+            input.parse::<syn::Token![=]>()?;
+            return Ok(SetDisplay::With(input.parse()?));
+        }
+        let template = input.parse::<syn::LitStr>().map_err(|error| {
+            syn::parse::Error::new(
+                error.span(),
+                format!(
+                    "Expected a template like `{0}(\"..{{}}..\")` or `{0}(with = path::to_fn)` on a set.",
+                    DISPLAY_ATTRIBUTE_NAME
+                ),
+            )
+        })?;
+        Ok(SetDisplay::Template(template))
+    }
+}
+
 fn extract_display_attribute(
     attributes: &mut Vec<Attribute>,
 ) -> syn::Result<Option<DisplayAttribute>> {
@@ -576,18 +1054,91 @@ fn extract_cfg(attributes: Vec<Attribute>) -> (Vec<Attribute>, Vec<Attribute>) {
     (attributes, cfgs)
 }
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone)]
 pub(crate) struct AstInlineErrorVariantField {
     pub(crate) name: Ident,
     pub(crate) r#type: syn::Type,
+    /// e.g. `hint: Option<String> = None`. Used to fill the field when converting from a variant without it.
+    pub(crate) default: Option<syn::Expr>,
+    pub(crate) redact: Option<Redact>,
 }
 
 impl Parse for AstInlineErrorVariantField {
     fn parse(input: ParseStream) -> Result<Self> {
+        let attributes = input.call(Attribute::parse_outer)?;
+        let redact = extract_redact(attributes)?;
         let name: Ident = input.parse()?;
         let _: syn::Token![:] = input.parse()?;
         let r#type: syn::Type = input.parse()?;
-        Ok(AstInlineErrorVariantField { name, r#type })
+        let default = if input.peek(syn::Token![=]) {
+            input.parse::<syn::Token![=]>()?;
+            Some(input.parse::<syn::Expr>()?)
+        } else {
+            None
+        };
+        Ok(AstInlineErrorVariantField {
+            name,
+            r#type,
+            default,
+            redact,
+        })
+    }
+}
+
+/// `#[redact]` or `#[redact(with = path::to_fn)]` on a field. The field is rendered as `<redacted>` in `Debug` and
+/// `Display`, or with the function, called as `fn(&Field, &mut core::fmt::Formatter) -> core::fmt::Result`.
+#[derive(Clone)]
+pub(crate) struct Redact {
+    pub(crate) with: Option<syn::Path>,
+}
+
+impl Parse for Redact {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse::<Ident>()?;
+        if name != "with" {
+            return Err(syn::parse::Error::new(
+                name.span(),
+                format!("`{name}` is not a valid option for `{REDACT_ATTRIBUTE_NAME}`. Expected `with`."),
+            ));
+        }
+        input.parse::<syn::Token![=]>()?;
+        Ok(Redact {
+            with: Some(input.parse()?),
+        })
+    }
+}
+
+/// Fields only support `#[redact(..)]`
+fn extract_redact(attributes: Vec<Attribute>) -> syn::Result<Option<Redact>> {
+    let mut redact = None;
+    for attribute in attributes {
+        if !attribute.path().is_ident(REDACT_ATTRIBUTE_NAME) {
+            return Err(syn::parse::Error::new_spanned(
+                attribute,
+                format!(
+                    "Only `#[{}]` is supported on fields.",
+                    REDACT_ATTRIBUTE_NAME
+                ),
+            ));
+        }
+        if redact.is_some() {
+            return Err(syn::parse::Error::new_spanned(
+                attribute,
+                format!("More than one `{}` attribute found", REDACT_ATTRIBUTE_NAME),
+            ));
+        }
+        redact = Some(match &attribute.meta {
+            syn::Meta::Path(_) => Redact { with: None },
+            _ => attribute.parse_args::<Redact>()?,
+        });
+    }
+    Ok(redact)
+}
+
+/// The default does not change the shape of the field
+impl PartialEq for AstInlineErrorVariantField {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.r#type == other.r#type
     }
 }
 
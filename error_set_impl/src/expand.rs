@@ -4,10 +4,14 @@
 use std::collections::{HashMap, HashSet};
 
 use proc_macro2::TokenStream;
-use quote::{quote, TokenStreamExt};
+use quote::{format_ident, quote, TokenStreamExt};
 use syn::{Attribute, Ident, Lit, TypeParam};
 
-use crate::ast::{AstInlineErrorVariantField, Disabled, DisplayAttribute};
+use crate::ast::{
+    AstInlineErrorVariantField, Convert, Disabled, DisplayAttribute, IoAttributes, Redact,
+    SetDisplay,
+};
+use crate::format_str;
 
 /// Expand the [ErrorEnum]s into code.
 pub(crate) fn expand(error_enums: Vec<ErrorEnum>) -> TokenStream {
@@ -23,7 +27,19 @@ pub(crate) fn expand(error_enums: Vec<ErrorEnum>) -> TokenStream {
             if checking_index == building_index {
                 continue;
             }
+            // An explicit `#[convert(from = ..)]` replaces the automatic conversion
+            let checking_name = &graph[checking_index].error_enum.error_name;
+            if graph[building_index]
+                .error_enum
+                .converts
+                .iter()
+                .any(|e| &e.from == checking_name)
+            {
+                continue;
+            }
 
+            let strict = graph[building_index].error_enum.strict_match
+                || graph[checking_index].error_enum.strict_match;
             let mut variant_mappings = Vec::new();
             'look_for_next_variant_match: for (checking_variant_index, checking_variant) in graph
                 [checking_index]
@@ -38,7 +54,9 @@ pub(crate) fn expand(error_enums: Vec<ErrorEnum>) -> TokenStream {
                     .iter()
                     .enumerate()
                 {
-                    if is_conversion_target(checking_variant, building_variant) {
+                    if !building_variant.disable_from()
+                        && is_conversion_target(checking_variant, building_variant, strict)
+                    {
                         variant_mappings.push((checking_variant_index, building_variant_index));
                         continue 'look_for_next_variant_match;
                     }
@@ -63,9 +81,15 @@ fn add_code_for_node(
     token_stream: &mut TokenStream,
 ) {
     add_enum(error_enum_node, token_stream);
+    impl_debug(error_enum_node, token_stream);
     impl_error(error_enum_node, token_stream);
     impl_display(error_enum_node, token_stream);
     impl_froms(error_enum_node, graph, token_stream);
+    impl_converts(error_enum_node, graph, token_stream);
+    impl_into_io(error_enum_node, token_stream);
+    impl_constructors(error_enum_node, token_stream);
+    add_context_selectors(error_enum_node, graph, token_stream);
+    add_result_alias(error_enum_node, token_stream);
 }
 
 fn add_enum(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream) {
@@ -76,11 +100,6 @@ fn add_enum(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream
 
     let enum_name = &error_enum.error_name;
     let error_variants = &error_enum.error_variants;
-    #[cfg(feature = "dev")]
-    assert!(
-        !error_variants.is_empty(),
-        "Error variants should not be empty"
-    );
     let mut error_variant_tokens = TokenStream::new();
     for variant in error_variants {
         match variant {
@@ -141,20 +160,410 @@ fn add_enum(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream
     }
     let attributes = &error_enum.attributes;
     let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
-    let debug = if error_enum.disabled.debug {
+    // With redacted fields, `Debug` is implemented in `impl_debug`
+    let debug = if error_enum.disabled.debug || has_redacted_fields(error_enum) {
         quote! {}
     } else {
         quote! { #[derive(Debug)] }
     };
+    let derives = &error_enum.derives;
+    let derives = if derives.is_empty() {
+        quote! {}
+    } else {
+        quote! { #[derive(#(#derives),*)] }
+    };
+    let non_exhaustive = if error_enum.non_exhaustive {
+        quote! { #[non_exhaustive] }
+    } else {
+        quote! {}
+    };
+    let vis = &error_enum.vis;
     token_stream.append_all(quote::quote! {
         #(#attributes)*
         #debug
-        pub enum #enum_name #impl_generics {
+        #derives
+        #non_exhaustive
+        #vis enum #enum_name #impl_generics {
             #error_variant_tokens
         }
     });
 }
 
+/// e.g. `pub fn missing_field(field: impl Into<String>) -> Self` for `MissingField { field: String }`.
+/// Fields with a default value are not parameters.
+fn impl_constructors(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream) {
+    let error_enum = &error_enum_node.error_enum;
+    if error_enum.disabled.constructors {
+        return;
+    }
+    let enum_name = &error_enum.error_name;
+    let vis = &error_enum.vis;
+    let mut constructors = TokenStream::new();
+    for variant in &error_enum.error_variants {
+        let (ErrorVariant::Struct(_) | ErrorVariant::SourceStruct(_)) = variant else {
+            continue;
+        };
+        let name = variant.name();
+        let fields = variant.fields().expect("struct variants have fields");
+        let constructor_name = constructor_name(variant);
+        let cfg_attributes = variant.cfg_attributes();
+        let source_param = variant
+            .source_type()
+            .map(|source_type| quote! { source: impl Into<#source_type>, });
+        let source_init = variant
+            .source_type()
+            .map(|_| quote! { source: source.into(), });
+        let params = fields.iter().filter(|e| e.default.is_none()).map(|field| {
+            let field_name = &field.name;
+            let field_type = &field.r#type;
+            quote! { #field_name: impl Into<#field_type> }
+        });
+        let inits = fields.iter().map(|field| {
+            let field_name = &field.name;
+            match &field.default {
+                Some(default) => quote! { #field_name: #default },
+                None => quote! { #field_name: #field_name.into() },
+            }
+        });
+        let doc = format!("Creates [`{enum_name}::{name}`].");
+        constructors.append_all(quote! {
+            #(#cfg_attributes)*
+            #[doc = #doc]
+            #[allow(dead_code)]
+            #vis fn #constructor_name(#source_param #(#params),*) -> Self {
+                #enum_name::#name {
+                    #source_init
+                    #(#inits),*
+                }
+            }
+        });
+    }
+    if constructors.is_empty() {
+        return;
+    }
+    let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
+    token_stream.append_all(quote! {
+        impl #impl_generics #enum_name #ty_generics {
+            #constructors
+        }
+    });
+}
+
+/// The `snake_case` name of the constructor of [variant], e.g. `missing_field` for `MissingField`
+pub(crate) fn constructor_name(variant: &ErrorVariant) -> Ident {
+    let name = variant.name();
+    let chars = name.to_string().chars().collect::<Vec<_>>();
+    let mut snake_case = String::new();
+    for (index, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let previous = index.checked_sub(1).map(|e| chars[e]);
+            let next = chars.get(index + 1);
+            // e.g. `IoError` -> `io_error` and `HTTPError` -> `http_error`
+            let is_word_start = previous.is_some_and(|previous| {
+                previous.is_lowercase()
+                    || previous.is_ascii_digit()
+                    || (previous.is_uppercase() && next.is_some_and(|e| e.is_lowercase()))
+            });
+            if is_word_start {
+                snake_case.push('_');
+            }
+            snake_case.extend(c.to_lowercase());
+        } else {
+            snake_case.push(*c);
+        }
+    }
+    // Cannot be raw identifiers
+    if matches!(snake_case.as_str(), "crate" | "self" | "super") {
+        snake_case.push('_');
+    }
+    // e.g. `Type` -> `r#type`
+    syn::parse_str::<Ident>(&snake_case)
+        .map(|e| Ident::new(&e.to_string(), name.span()))
+        .unwrap_or_else(|_| Ident::new_raw(&snake_case, name.span()))
+}
+
+/// e.g. `pub struct ReadCtx<__T0> { pub path: __T0 }` for `Read(std::io::Error) { path: PathBuf }`, used with
+/// `result.context(ReadCtx { path })`. Only generated for variants declared in the set, so sets that aggregate the
+/// variant do not redefine the selector. If several sets declare the variant, the first one declares the selector and
+/// each gets its own `IntoError` impl. Fields with a default value are not part of the selector. The impls refer to
+/// `::error_set::IntoError`, so they are not generated for sets with `#[disable(ContextSelectors)]`.
+fn add_context_selectors(
+    error_enum_node: &ErrorEnumGraphNode,
+    graph: &[ErrorEnumGraphNode],
+    token_stream: &mut TokenStream,
+) {
+    let error_enum = &error_enum_node.error_enum;
+    if error_enum.disabled.context_selectors {
+        return;
+    }
+    let enum_name = &error_enum.error_name;
+    let (_, ty_generics) = generic_tokens(&error_enum.generics);
+    let enum_generics = &error_enum.generics;
+    for variant in error_enum.own_variants() {
+        let ErrorVariant::SourceStruct(_) = variant else {
+            continue;
+        };
+        let selector_name = context_selector_name(variant);
+        let (first_error_enum, first_variant) = graph
+            .iter()
+            .filter(|e| !e.error_enum.disabled.context_selectors)
+            .flat_map(|e| {
+                e.error_enum
+                    .own_variants()
+                    .iter()
+                    .map(move |v| (&e.error_enum, v))
+            })
+            .find(|(_, e)| {
+                matches!(e, ErrorVariant::SourceStruct(_))
+                    && context_selector_name(e) == selector_name
+            })
+            .expect("the variant is declared in the graph");
+        let name = variant.name();
+        let source_type = variant
+            .source_type()
+            .expect("source struct variants have a source");
+        let fields = variant
+            .fields()
+            .expect("source struct variants have fields");
+        let cfg_attributes = variant.cfg_attributes();
+        // Dev Note: validated in `validate` that every set declares the same fields without a default
+        let selector_fields = first_variant
+            .fields()
+            .expect("source struct variants have fields")
+            .iter()
+            .filter(|e| e.default.is_none())
+            .enumerate()
+            .map(|(index, field)| (field, format_ident!("__T{index}")))
+            .collect::<Vec<_>>();
+        let params = selector_fields
+            .iter()
+            .map(|(_, param)| param)
+            .collect::<Vec<_>>();
+        let selector_generics = (!params.is_empty()).then(|| quote! { <#(#params),*> });
+        let bounds = selector_fields.iter().map(|(selector_field, param)| {
+            let field_type = &fields
+                .iter()
+                .find(|e| e.name == selector_field.name)
+                .expect("the fields are validated to be the same")
+                .r#type;
+            quote! { #param: Into<#field_type> }
+        });
+        if std::ptr::eq(first_variant, variant) {
+            let vis = &first_error_enum.vis;
+            let declaration = if selector_fields.is_empty() {
+                quote! { ; }
+            } else {
+                let declared_fields = selector_fields.iter().map(|(field, param)| {
+                    let field_name = &field.name;
+                    quote! { #vis #field_name: #param }
+                });
+                quote! { { #(#declared_fields),* } }
+            };
+            let doc = format!(
+                "Context selector for [`{enum_name}::{name}`]. Use with `result.context({selector_name} {{ .. }})`."
+            );
+            token_stream.append_all(quote! {
+                #(#cfg_attributes)*
+                #[doc = #doc]
+                #[derive(Debug, Clone, Copy)]
+                #vis struct #selector_name #selector_generics #declaration
+            });
+        }
+        let inits = fields.iter().map(|field| {
+            let field_name = &field.name;
+            match &field.default {
+                Some(default) => quote! { #field_name: #default },
+                None => quote! { #field_name: self.#field_name.into() },
+            }
+        });
+        token_stream.append_all(quote! {
+            #(#cfg_attributes)*
+            impl<#(#enum_generics,)* #(#bounds),*> ::error_set::IntoError<#enum_name #ty_generics> for #selector_name #selector_generics {
+                type Source = #source_type;
+
+                fn into_error(self, source: #source_type) -> #enum_name #ty_generics {
+                    #enum_name::#name {
+                        source,
+                        #(#inits),*
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// e.g. `ReadCtx` for `Read`
+pub(crate) fn context_selector_name(variant: &ErrorVariant) -> Ident {
+    let name = variant.name();
+    format_ident!("{name}Ctx")
+}
+
+/// e.g. `pub type MediaResult<T> = core::result::Result<T, MediaError>;`
+fn add_result_alias(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream) {
+    let error_enum = &error_enum_node.error_enum;
+    if !error_enum.result_alias {
+        return;
+    }
+    let enum_name = &error_enum.error_name;
+    let enum_name_str = enum_name.to_string();
+    let alias_name = Ident::new(
+        &format!(
+            "{}Result",
+            enum_name_str
+                .strip_suffix("Error")
+                .filter(|e| !e.is_empty())
+                .unwrap_or(&enum_name_str)
+        ),
+        enum_name.span(),
+    );
+    let vis = &error_enum.vis;
+    let generic_idents = error_enum
+        .generics
+        .iter()
+        .map(|e| &e.ident)
+        .collect::<Vec<_>>();
+    // Avoid a collision with a generic of the set
+    let ok_type = if generic_idents.iter().any(|e| *e == "T") {
+        quote! { __T }
+    } else {
+        quote! { T }
+    };
+    let ty_generics = if generic_idents.is_empty() {
+        quote! {}
+    } else {
+        quote! { <#(#generic_idents),*> }
+    };
+    token_stream.append_all(quote::quote! {
+        #vis type #alias_name<#ok_type, #(#generic_idents),*> = core::result::Result<#ok_type, #enum_name #ty_generics>;
+    });
+}
+
+/// `Debug` for sets with `#[redact]` fields, otherwise `Debug` is derived.
+fn impl_debug(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream) {
+    let error_enum = &error_enum_node.error_enum;
+    if error_enum.disabled.debug || !has_redacted_fields(error_enum) {
+        return;
+    }
+    let enum_name = &error_enum.error_name;
+    let mut error_variant_tokens = TokenStream::new();
+    for variant in &error_enum.error_variants {
+        let name = variant.name();
+        let name_str = name.to_string();
+        let cfg_attributes = variant.cfg_attributes();
+        let debug_fields = |fields: &Vec<AstInlineErrorVariantField>| {
+            fields
+                .iter()
+                .map(|field| {
+                    let field_name = &field.name;
+                    let field_name_str = field_name.to_string();
+                    match &field.redact {
+                        Some(redact) => {
+                            let redacted = redacted_value(field_name, redact);
+                            quote! { .field(#field_name_str, &#redacted) }
+                        }
+                        None => quote! { .field(#field_name_str, #field_name) },
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        let arm = match variant {
+            ErrorVariant::Named(_) => quote! {
+                #enum_name::#name => f.write_str(#name_str),
+            },
+            ErrorVariant::Struct(r#struct) => {
+                let field_names = r#struct.fields.iter().map(|e| &e.name);
+                let debug_fields = debug_fields(&r#struct.fields);
+                quote! {
+                    #enum_name::#name { #(ref #field_names),* } => f.debug_struct(#name_str)#(#debug_fields)*.finish(),
+                }
+            }
+            ErrorVariant::SourceStruct(source_struct) => {
+                let field_names = source_struct.fields.iter().map(|e| &e.name);
+                let debug_fields = debug_fields(&source_struct.fields);
+                quote! {
+                    #enum_name::#name { ref source, #(ref #field_names),* } => f.debug_struct(#name_str).field("source", source)#(#debug_fields)*.finish(),
+                }
+            }
+            ErrorVariant::SourceTuple(_) => quote! {
+                #enum_name::#name(ref source) => f.debug_tuple(#name_str).field(source).finish(),
+            },
+        };
+        error_variant_tokens.append_all(quote! {
+            #(#cfg_attributes)*
+            #arm
+        });
+    }
+    let redacted_helper = redacted_helper();
+    let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
+    let generic_idents = error_enum.generics.iter().map(|e| &e.ident);
+    token_stream.append_all(quote::quote! {
+        impl #impl_generics core::fmt::Debug for #enum_name #ty_generics
+        where
+            #(#generic_idents: core::fmt::Debug),*
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                #redacted_helper
+                match *self {
+                    #error_variant_tokens
+                }
+            }
+        }
+    });
+}
+
+fn has_redacted_fields(error_enum: &ErrorEnum) -> bool {
+    error_enum
+        .error_variants
+        .iter()
+        .filter_map(|e| e.fields())
+        .flatten()
+        .any(|e| e.redact.is_some())
+}
+
+/// Renders a `#[redact]` field as `<redacted>` or with its `with` function, in both `Debug` and `Display`.
+fn redacted_helper() -> TokenStream {
+    quote! {
+        struct Redacted<'a, T: ?Sized>(
+            &'a T,
+            Option<fn(&T, &mut core::fmt::Formatter) -> core::fmt::Result>,
+        );
+        impl<T: ?Sized> core::fmt::Debug for Redacted<'_, T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                match self.1 {
+                    Some(with) => with(self.0, f),
+                    None => f.write_str("<redacted>"),
+                }
+            }
+        }
+        impl<T: ?Sized> core::fmt::Display for Redacted<'_, T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                core::fmt::Debug::fmt(self, f)
+            }
+        }
+    }
+}
+
+fn redacted_value(field_name: &Ident, redact: &Redact) -> TokenStream {
+    match &redact.with {
+        Some(with) => quote! { Redacted(#field_name, Some(#with)) },
+        None => quote! { Redacted(#field_name, None) },
+    }
+}
+
+/// Shadows the `#[redact]` fields bound in a match arm, so the display message cannot reveal them.
+fn redacted_lets(fields: &[AstInlineErrorVariantField]) -> TokenStream {
+    let lets = fields.iter().filter_map(|field| {
+        let redact = field.redact.as_ref()?;
+        let field_name = &field.name;
+        let redacted = redacted_value(field_name, redact);
+        Some(quote! {
+            #[allow(unused_variables)]
+            let #field_name = #redacted;
+        })
+    });
+    quote! { #(#lets)* }
+}
+
 fn impl_error(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream) {
     let ErrorEnumGraphNode {
         error_enum,
@@ -167,7 +576,15 @@ fn impl_error(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStre
     let mut source_match_branches = TokenStream::new();
     let mut has_source_match_branches = false;
     for variant in &error_enum.error_variants {
-        if is_source_tuple_type(variant) {
+        if is_wrapped_type(variant) {
+            has_source_match_branches = true;
+            let name = &variant.name();
+            let cfg_attributes = &variant.cfg_attributes();
+            source_match_branches.append_all(quote::quote! {
+                #(#cfg_attributes)*
+                #enum_name::#name(ref source) => Some(source),
+            });
+        } else if is_source_tuple_type(variant) {
             has_source_match_branches = true;
             let name = &variant.name();
             let cfg_attributes = &variant.cfg_attributes();
@@ -216,11 +633,6 @@ fn impl_display(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenSt
     }
     let enum_name = &error_enum.error_name;
     let error_variants = &error_enum.error_variants;
-    #[cfg(feature = "dev")]
-    assert!(
-        !error_variants.is_empty(),
-        "Error variants should not be empty"
-    );
     let mut error_variant_tokens = TokenStream::new();
     for variant in error_variants {
         let right_side: TokenStream;
@@ -232,30 +644,43 @@ fn impl_display(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenSt
                 right_side = quote::quote! {
                     write!(f, "{}", concat!(stringify!(#enum_name), "::", stringify!(#name)))
                 };
-            } else if let Some(string) = extract_string_if_str_literal(tokens.clone()) {
-                // e.g. `"{}"`
-                if is_format_str(&string) {
-                    if is_source_tuple_type(variant) {
-                        right_side = quote::quote! {
-                            write!(f, #tokens, source)
-                        };
-                    } else {
-                        right_side = quote::quote! {
-                            write!(f, #tokens)
-                        };
-                    }
-                } else {
-                    // e.g. `"literal str"`
-                    right_side = quote::quote! {
-                        write!(f, "{}", #tokens)
-                    };
-                }
+            } else if let Some(raw) = raw_display(tokens.clone()) {
+                // e.g. `raw = "{not formatted}"`
+                right_side = quote::quote! {
+                    write!(f, "{}", #raw)
+                };
+            } else if let Ok(format_str) = syn::parse2::<syn::LitStr>(tokens.clone()) {
+                // e.g. `"{}"`. Dev Note: validated in `validate`
+                let format_str = named_format_str(&format_str, variant)
+                    .expect("display format strings are validated");
+                right_side = quote::quote! {
+                    write!(f, #format_str)
+                };
             } else {
                 // e.g. `"field: {}", source.field`
                 right_side = quote::quote! {
                     write!(f, #tokens)
                 };
             }
+        } else if let Some((doc, span)) = error_enum
+            .doc_display
+            .then(|| doc_message(variant.attributes()))
+            .flatten()
+        {
+            // e.g. `/// User `{name}` does not exist`. Printed as is, if it is not a valid format string.
+            let doc = syn::LitStr::new(&doc, span);
+            match named_format_str(&doc, variant) {
+                Ok(format_str) => {
+                    right_side = quote::quote! {
+                        write!(f, #format_str)
+                    };
+                }
+                Err(_) => {
+                    right_side = quote::quote! {
+                        write!(f, "{}", #doc)
+                    };
+                }
+            }
         } else {
             if is_source_tuple_type(variant) {
                 right_side = quote::quote! {
@@ -279,17 +704,19 @@ fn impl_display(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenSt
             ErrorVariant::Struct(r#struct) => {
                 let cfg_attributes = &r#struct.cfg_attributes;
                 let field_names = r#struct.fields.iter().map(|e| &e.name);
+                let redacted = redacted_lets(&r#struct.fields);
                 error_variant_tokens.append_all(quote::quote! {
                     #(#cfg_attributes)*
-                    #enum_name::#name { #(ref #field_names),*  } =>  #right_side,
+                    #enum_name::#name { #(ref #field_names),*  } =>  { #redacted #right_side },
                 });
             }
             ErrorVariant::SourceStruct(source_struct) => {
                 let cfg_attributes = &source_struct.cfg_attributes;
                 let field_names = source_struct.fields.iter().map(|e| &e.name);
+                let redacted = redacted_lets(&source_struct.fields);
                 error_variant_tokens.append_all(quote::quote! {
                     #(#cfg_attributes)*
-                    #enum_name::#name { ref source, #(ref #field_names),* } =>  #right_side,
+                    #enum_name::#name { ref source, #(ref #field_names),* } =>  { #redacted #right_side },
                 });
             }
             ErrorVariant::SourceTuple(source_tuple) => {
@@ -301,14 +728,41 @@ fn impl_display(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenSt
             }
         }
     }
+    let redacted_helper = if has_redacted_fields(error_enum) {
+        redacted_helper()
+    } else {
+        quote! {}
+    };
+    let body = match &error_enum.display {
+        None => quote::quote! {
+            #redacted_helper
+            match *self {
+                #error_variant_tokens
+            }
+        },
+        // The message of the variant is written where the `{}` of the template is
+        Some(SetDisplay::Template(template)) => quote::quote! {
+            #redacted_helper
+            struct Message<F: Fn(&mut core::fmt::Formatter) -> core::fmt::Result>(F);
+            impl<F: Fn(&mut core::fmt::Formatter) -> core::fmt::Result> core::fmt::Display for Message<F> {
+                fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                    (self.0)(f)
+                }
+            }
+            write!(f, #template, Message(|f: &mut core::fmt::Formatter| match *self {
+                #error_variant_tokens
+            }))
+        },
+        Some(SetDisplay::With(path)) => quote::quote! {
+            #path(self, f)
+        },
+    };
     let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
     token_stream.append_all(quote::quote! {
         impl #impl_generics core::fmt::Display for #enum_name #ty_generics {
             #[inline]
             fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-                match *self {
-                    #error_variant_tokens
-                }
+                #body
             }
         }
     });
@@ -359,77 +813,22 @@ fn impl_froms(
                 let from = from_error_enum_variant.name();
                 let to = error_enum_variant.name();
                 assert!(
-                    is_conversion_target(from_error_enum_variant, error_enum_variant),
+                    is_conversion_target(
+                        from_error_enum_variant,
+                        error_enum_variant,
+                        error_enum.strict_match || from_error_enum.strict_match
+                    ),
                     "Not a valid conversion target\n\nfrom:\n\n{from}\n\nto:\n\n{to}"
                 );
             }
             all_cfg_attributes.extend(from_error_enum_variant.cfg_attributes().clone());
             all_cfg_attributes.extend(error_enum_variant.cfg_attributes().clone());
-            let arm: Option<TokenStream> = match (from_error_enum_variant, error_enum_variant) {
-                (ErrorVariant::Named(this), ErrorVariant::Named(that)) => Some(name_to_name(
-                    from_error_enum_name,
-                    &this.name,
-                    error_enum_name,
-                    &that.name,
-                )),
-                (ErrorVariant::Named(this), ErrorVariant::Struct(that)) => None,
-                (ErrorVariant::Named(this), ErrorVariant::SourceStruct(that)) => None,
-                (ErrorVariant::Named(this), ErrorVariant::SourceTuple(that)) => None,
-                (ErrorVariant::Struct(this), ErrorVariant::Named(that)) => None,
-                (ErrorVariant::Struct(this), ErrorVariant::Struct(that)) => Some(struct_to_struct(
-                    from_error_enum_name,
-                    &this.name,
-                    &this.fields,
-                    error_enum_name,
-                    &that.name,
-                    &that.fields,
-                )),
-                (ErrorVariant::Struct(this), ErrorVariant::SourceStruct(that)) => None,
-                (ErrorVariant::Struct(this), ErrorVariant::SourceTuple(that)) => None,
-                (ErrorVariant::SourceStruct(this), ErrorVariant::Named(that)) => None,
-                (ErrorVariant::SourceStruct(this), ErrorVariant::Struct(that)) => None,
-                (ErrorVariant::SourceStruct(this), ErrorVariant::SourceStruct(that)) => {
-                    Some(source_struct_to_source_struct(
-                        from_error_enum_name,
-                        &this.name,
-                        &this.fields,
-                        error_enum_name,
-                        &that.name,
-                        &that.fields,
-                    ))
-                }
-                (ErrorVariant::SourceStruct(this), ErrorVariant::SourceTuple(that)) => {
-                    Some(source_struct_to_source_tuple(
-                        from_error_enum_name,
-                        &this.name,
-                        &this.fields,
-                        error_enum_name,
-                        &that.name,
-                    ))
-                }
-                (ErrorVariant::SourceTuple(this), ErrorVariant::Named(that)) => None,
-                (ErrorVariant::SourceTuple(this), ErrorVariant::Struct(that)) => None,
-                (ErrorVariant::SourceTuple(this), ErrorVariant::SourceStruct(that)) => {
-                    if that.fields.is_empty() {
-                        Some(source_tuple_to_source_only_struct(
-                            from_error_enum_name,
-                            &this.name,
-                            error_enum_name,
-                            &that.name,
-                        ))
-                    } else {
-                        None
-                    }
-                }
-                (ErrorVariant::SourceTuple(this), ErrorVariant::SourceTuple(that)) => {
-                    Some(source_tuple_to_source_tuple(
-                        from_error_enum_name,
-                        &this.name,
-                        error_enum_name,
-                        &that.name,
-                    ))
-                }
-            };
+            let arm = conversion_arm(
+                from_error_enum_name,
+                from_error_enum_variant,
+                error_enum_name,
+                error_enum_variant,
+            );
             if let Some(arm) = arm {
                 error_branch_tokens.append_all(arm);
             }
@@ -459,12 +858,247 @@ fn impl_froms(
         });
     }
 
-    // Do not impl `From` for source where source is the same between multiple variants
+    let source_type_to_error_variants = source_froms(error_enum);
+    // Add `From`'s for all valid variants that are wrappers around source errors.
+    for (source_type, error_variant) in source_type_to_error_variants.iter() {
+        if is_source_tuple_type(error_variant) || is_source_only_struct_type(error_variant) {
+            let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
+            let cfg_attributes = &error_variant.cfg_attributes();
+            let body = from_io_dispatch(
+                error_enum,
+                source_type,
+                from_source(error_enum_name, error_variant),
+            );
+            token_stream.append_all(quote::quote! {
+                #(#cfg_attributes)*
+                impl #impl_generics From<#source_type> for #error_enum_name #ty_generics {
+                    fn from(error: #source_type) -> Self {
+                        #body
+                    }
+                }
+            });
+        }
+    }
+
+    // Add `From`'s through `wrap(..)` variants, for the source errors the wrapped set has a `From` for.
+    // Only when a single wrapped set accepts the source and this set does not have its own variant for it.
+    let own_source_types = error_enum
+        .error_variants
+        .iter()
+        .filter_map(|e| e.source_type())
+        .collect::<HashSet<_>>();
+    let mut source_type_to_wrapped_variants: Vec<(
+        &syn::TypePath,
+        Vec<(&ErrorVariant, &ErrorVariant)>,
+    )> = Vec::new();
+    for wrapped_variant in error_enum.error_variants.iter() {
+        if !is_wrapped_type(wrapped_variant) {
+            continue;
+        }
+        let Some(wrapped_error_enum) = graph
+            .iter()
+            .map(|e| &e.error_enum)
+            .find(|e| e.error_name == *wrapped_variant.name() && e.generics.is_empty())
+        else {
+            continue;
+        };
+        for (source_type, inner_variant) in source_froms(wrapped_error_enum) {
+            if own_source_types.contains(source_type)
+                || froms_to_disable.contains(source_type)
+                || is_infallible_type(source_type)
+            {
+                continue;
+            }
+            match source_type_to_wrapped_variants
+                .iter_mut()
+                .find(|(e, _)| *e == source_type)
+            {
+                Some((_, wrapped_variants)) => {
+                    wrapped_variants.push((wrapped_variant, inner_variant))
+                }
+                None => source_type_to_wrapped_variants
+                    .push((source_type, vec![(wrapped_variant, inner_variant)])),
+            }
+        }
+    }
+    for (source_type, wrapped_variants) in source_type_to_wrapped_variants {
+        let [(wrapped_variant, inner_variant)] = &*wrapped_variants else {
+            continue;
+        };
+        let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
+        let variant_name = wrapped_variant.name();
+        let cfg_attributes = wrapped_variant
+            .cfg_attributes()
+            .iter()
+            .chain(inner_variant.cfg_attributes().iter());
+        token_stream.append_all(quote::quote! {
+            #(#cfg_attributes)*
+            impl #impl_generics From<#source_type> for #error_enum_name #ty_generics {
+                fn from(error: #source_type) -> Self {
+                    #error_enum_name::#variant_name(#variant_name::from(error))
+                }
+            }
+        });
+    }
+
+    // Add `From<Infallible>`, so `?` works on infallible results. Unless this set has its own variant for it,
+    // or a `From<T>` for a generic `T`, which would overlap.
+    let is_infallible_from_taken = froms_to_disable.iter().any(is_infallible_type)
+        || source_type_to_error_variants.keys().any(|e| {
+            is_infallible_type(e)
+                || error_enum
+                    .generics
+                    .iter()
+                    .any(|generic| e.path.is_ident(&generic.ident))
+        });
+    if !is_infallible_from_taken {
+        let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
+        token_stream.append_all(quote::quote! {
+            impl #impl_generics From<core::convert::Infallible> for #error_enum_name #ty_generics {
+                fn from(error: core::convert::Infallible) -> Self {
+                    match error {}
+                }
+            }
+        });
+    }
+}
+
+/// e.g. `MediaError::Read(error)` or `MediaError::Read { source: error }`
+fn from_source(error_enum_name: &Ident, error_variant: &ErrorVariant) -> TokenStream {
+    let variant_name = error_variant.name();
+    if is_source_tuple_type(error_variant) {
+        quote! { #error_enum_name::#variant_name(error) }
+    } else {
+        quote! { #error_enum_name::#variant_name { source: error } }
+    }
+}
+
+/// Dispatches to the `#[from_io(kind = ..)]` variants of [source_type] by `error.kind()`, and to [fallback] for any
+/// other kind. Just [fallback] if there are none.
+fn from_io_dispatch(
+    error_enum: &ErrorEnum,
+    source_type: &syn::TypePath,
+    fallback: TokenStream,
+) -> TokenStream {
+    let arms = error_enum
+        .error_variants
+        .iter()
+        .filter(|e| {
+            !e.io().from_kinds.is_empty()
+                && !e.disable_from()
+                && e.source_type()
+                    .is_some_and(|e| is_same_source_type(e, source_type, error_enum.strict_match))
+        })
+        .map(|error_variant| {
+            let cfg_attributes = error_variant.cfg_attributes();
+            let kinds = &error_variant.io().from_kinds;
+            let target = from_source(&error_enum.error_name, error_variant);
+            quote! {
+                #(#cfg_attributes)*
+                #(::std::io::ErrorKind::#kinds)|* => #target,
+            }
+        })
+        .collect::<Vec<_>>();
+    if arms.is_empty() {
+        return fallback;
+    }
+    quote! {
+        match error.kind() {
+            #(#arms)*
+            _ => #fallback,
+        }
+    }
+}
+
+/// `impl From<MediaError> for std::io::Error`, with the kind from `#[io_kind(..)]`. Variants with a
+/// `std::io::Error` source keep its kind, all others are `ErrorKind::Other`. The set is kept as the inner error.
+fn impl_into_io(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream) {
+    let error_enum = &error_enum_node.error_enum;
+    if !error_enum.into_io {
+        return;
+    }
+    let error_enum_name = &error_enum.error_name;
+    let mut arms = TokenStream::new();
+    for error_variant in &error_enum.error_variants {
+        let variant_name = error_variant.name();
+        let cfg_attributes = error_variant.cfg_attributes();
+        let kind = match (&error_variant.io().kind, error_variant.source_type()) {
+            (Some(kind), _) => quote! { ::std::io::ErrorKind::#kind },
+            (None, Some(source_type)) if is_io_error_type(source_type) => quote! { source.kind() },
+            (None, _) => continue,
+        };
+        let pattern = match error_variant {
+            ErrorVariant::Named(_) => quote! { #error_enum_name::#variant_name },
+            ErrorVariant::Struct(_) => quote! { #error_enum_name::#variant_name { .. } },
+            ErrorVariant::SourceStruct(_) => {
+                quote! { #error_enum_name::#variant_name { source, .. } }
+            }
+            ErrorVariant::SourceTuple(_) => quote! { #error_enum_name::#variant_name(source) },
+        };
+        arms.append_all(quote! {
+            #(#cfg_attributes)*
+            #[allow(unused_variables)]
+            #pattern => #kind,
+        });
+    }
+    let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
+    let where_clause = (!error_enum.generics.is_empty()).then(|| {
+        quote! { where #error_enum_name #ty_generics: std::error::Error + Send + Sync + 'static }
+    });
+    token_stream.append_all(quote! {
+        impl #impl_generics From<#error_enum_name #ty_generics> for ::std::io::Error #where_clause {
+            #[allow(unreachable_patterns)]
+            fn from(error: #error_enum_name #ty_generics) -> Self {
+                let kind = match &error {
+                    #arms
+                    _ => ::std::io::ErrorKind::Other,
+                };
+                ::std::io::Error::new(kind, error)
+            }
+        }
+    });
+}
+
+/// e.g. `std::io::Error` or `io::Error`
+fn is_io_error_type(type_path: &syn::TypePath) -> bool {
+    let segments = type_path
+        .path
+        .segments
+        .iter()
+        .rev()
+        .take(2)
+        .collect::<Vec<_>>();
+    matches!(&*segments, [error, io] if error.ident == "Error" && io.ident == "io")
+}
+
+/// The source types [error_enum] has a `From` implementation for, and the variant each is converted into.
+/// Sources shared between multiple variants, or disabled, are excluded. `#[from_io(..)]` variants are reached
+/// through the variant without it.
+fn source_froms(error_enum: &ErrorEnum) -> HashMap<&syn::TypePath, &ErrorVariant> {
     let mut source_type_to_error_variants = HashMap::new();
+    let froms_to_disable = match &error_enum.disabled.from {
+        Some(froms_to_disable) if froms_to_disable.is_empty() => {
+            return source_type_to_error_variants
+        }
+        Some(froms_to_disable) => &froms_to_disable[..],
+        None => &[],
+    };
+    // Do not impl `From` for source where source is the same between multiple variants
     let mut all_source_types = HashSet::new();
     for error_variant in error_enum.error_variants.iter() {
         if let Some(source_type) = error_variant.source_type() {
-            if froms_to_disable.contains(source_type) {
+            if error_variant.disable_from()
+                || !error_variant.io().from_kinds.is_empty()
+                || froms_to_disable.contains(source_type)
+            {
+                continue;
+            }
+            // An explicit `#[convert(from = ..)]` takes precedence
+            if error_enum
+                .converts
+                .iter()
+                .any(|e| source_type.path.is_ident(&e.from))
+            {
                 continue;
             }
             if all_source_types.contains(source_type) {
@@ -475,39 +1109,239 @@ fn impl_froms(
             }
         }
     }
+    source_type_to_error_variants.retain(|_, error_variant| {
+        is_source_tuple_type(error_variant) || is_source_only_struct_type(error_variant)
+    });
+    source_type_to_error_variants
+}
 
-    // Add `From`'s for all valid variants that are wrappers around source errors.
-    for error_variant in source_type_to_error_variants.values() {
-        let source_type = error_variant.source_type();
-        if is_source_tuple_type(error_variant) {
-            let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
-            let variant_name = &error_variant.name();
-            let cfg_attributes = &error_variant.cfg_attributes();
-            token_stream.append_all(quote::quote! {
-                #(#cfg_attributes)*
-                impl #impl_generics From<#source_type> for #error_enum_name #ty_generics {
-                    fn from(error: #source_type) -> Self {
-                        #error_enum_name::#variant_name(error)
-                    }
-                }
+fn impl_converts(
+    error_enum_node: &ErrorEnumGraphNode,
+    graph: &[ErrorEnumGraphNode],
+    token_stream: &mut TokenStream,
+) {
+    let error_enum = &error_enum_node.error_enum;
+    let error_enum_name = &error_enum.error_name;
+    for Convert {
+        from,
+        maps,
+        fallback,
+    } in error_enum.converts.iter()
+    {
+        let Some(from_error_enum) = graph
+            .iter()
+            .map(|e| &e.error_enum)
+            .find(|e| &e.error_name == from)
+        else {
+            // Dev Note: validated in `validate`
+            continue;
+        };
+        let from_error_enum_name = &from_error_enum.error_name;
+        let strict = error_enum.strict_match || from_error_enum.strict_match;
+        let mut all_cfg_attributes = HashSet::new();
+        let mut error_branch_tokens = TokenStream::new();
+        for from_error_enum_variant in from_error_enum.error_variants.iter() {
+            all_cfg_attributes.extend(from_error_enum_variant.cfg_attributes().clone());
+            if let Some(map) = maps
+                .iter()
+                .find(|e| &e.from_variant == from_error_enum_variant.name())
+            {
+                let pattern = variant_pattern(from_error_enum_name, from_error_enum_variant);
+                let expr = qualify_variant_expr(&map.expr, error_enum);
+                error_branch_tokens.append_all(quote::quote! {
+                    #pattern => #expr,
+                });
+                continue;
+            }
+            let arm = error_enum
+                .error_variants
+                .iter()
+                .filter(|e| !e.disable_from())
+                .find(|e| is_conversion_target(from_error_enum_variant, e, strict))
+                .and_then(|error_enum_variant| {
+                    all_cfg_attributes.extend(error_enum_variant.cfg_attributes().clone());
+                    conversion_arm(
+                        from_error_enum_name,
+                        from_error_enum_variant,
+                        error_enum_name,
+                        error_enum_variant,
+                    )
+                });
+            if let Some(arm) = arm {
+                error_branch_tokens.append_all(arm);
+            }
+        }
+        if let Some(fallback) = fallback {
+            let fallback_variant = error_enum
+                .error_variants
+                .iter()
+                .find(|e| e.name() == fallback);
+            let fallback_arm = match fallback_variant {
+                Some(variant) if is_source_tuple_type(variant) => quote::quote! {
+                    error => #error_enum_name::#fallback(error.into()),
+                },
+                Some(variant) if is_source_only_struct_type(variant) => quote::quote! {
+                    error => #error_enum_name::#fallback { source: error.into() },
+                },
+                _ => quote::quote! {
+                    _ => #error_enum_name::#fallback,
+                },
+            };
+            error_branch_tokens.append_all(quote::quote! {
+                #[allow(unreachable_patterns)]
+                #fallback_arm
             });
-        } else if is_source_only_struct_type(error_variant) {
-            let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
-            let variant_name = &error_variant.name();
-            let cfg_attributes = &error_variant.cfg_attributes();
-            token_stream.append_all(quote::quote! {
-                #(#cfg_attributes)*
-                impl #impl_generics From<#source_type> for #error_enum_name #ty_generics {
-                    fn from(error: #source_type) -> Self {
-                        #error_enum_name::#variant_name { source: error }
+        }
+        let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
+        let (_, from_ty_generics) = generic_tokens(&from_error_enum.generics);
+        let all_cfg_attributes = all_cfg_attributes.iter();
+        token_stream.append_all(quote::quote! {
+            #(#all_cfg_attributes)*
+            impl #impl_generics From<#from_error_enum_name #from_ty_generics> for #error_enum_name #ty_generics {
+                #[allow(unused_variables)]
+                fn from(error: #from_error_enum_name #from_ty_generics) -> Self {
+                    match error {
+                        #error_branch_tokens
                     }
                 }
-            });
+            }
+        });
+    }
+}
+
+/// A pattern for [variant] that binds all of its fields, e.g. `X::A { source, field }`.
+fn variant_pattern(enum_name: &Ident, variant: &ErrorVariant) -> TokenStream {
+    let name = variant.name();
+    match variant {
+        ErrorVariant::Named(_) => quote::quote! { #enum_name::#name },
+        ErrorVariant::Struct(r#struct) => {
+            let field_names = r#struct.fields.iter().map(|e| &e.name);
+            quote::quote! { #enum_name::#name { #(#field_names),* } }
+        }
+        ErrorVariant::SourceStruct(source_struct) => {
+            let field_names = source_struct.fields.iter().map(|e| &e.name);
+            quote::quote! { #enum_name::#name { source, #(#field_names),* } }
         }
+        ErrorVariant::SourceTuple(_) => quote::quote! { #enum_name::#name(source) },
     }
 }
+
+/// Variants in a `map(..)` expression may be written without the enum name, e.g. `NewVariant { .. }`.
+/// These are qualified with `Self`. Anything else, e.g. a free function or a field in scope, is left as is.
+fn qualify_variant_expr(expr: &syn::Expr, error_enum: &ErrorEnum) -> TokenStream {
+    let qualify_path = |path: &syn::Path| -> syn::Path {
+        match path.get_ident() {
+            Some(ident) if error_enum.error_variants.iter().any(|e| e.name() == ident) => {
+                syn::parse_quote! { Self::#path }
+            }
+            _ => path.clone(),
+        }
+    };
+    match expr {
+        syn::Expr::Path(expr_path) if expr_path.qself.is_none() => {
+            let path = qualify_path(&expr_path.path);
+            quote::quote! { #path }
+        }
+        syn::Expr::Struct(expr_struct) if expr_struct.qself.is_none() => {
+            let mut expr_struct = expr_struct.clone();
+            expr_struct.path = qualify_path(&expr_struct.path);
+            quote::quote! { #expr_struct }
+        }
+        syn::Expr::Call(expr_call) => match &*expr_call.func {
+            syn::Expr::Path(func) if func.qself.is_none() => {
+                let mut expr_call = expr_call.clone();
+                let path = qualify_path(&func.path);
+                *expr_call.func = syn::parse_quote! { #path };
+                quote::quote! { #expr_call }
+            }
+            _ => quote::quote! { #expr },
+        },
+        _ => quote::quote! { #expr },
+    }
+}
+
 //************************************************************************//
 
+fn conversion_arm(
+    from_error_enum_name: &Ident,
+    from_error_enum_variant: &ErrorVariant,
+    error_enum_name: &Ident,
+    error_enum_variant: &ErrorVariant,
+) -> Option<TokenStream> {
+    match (from_error_enum_variant, error_enum_variant) {
+        (ErrorVariant::Named(this), ErrorVariant::Named(that)) => Some(name_to_name(
+            from_error_enum_name,
+            &this.name,
+            error_enum_name,
+            &that.name,
+        )),
+        (ErrorVariant::Named(this), ErrorVariant::Struct(that)) => Some(name_to_struct(
+            from_error_enum_name,
+            &this.name,
+            error_enum_name,
+            &that.name,
+            &that.fields,
+        )),
+        (ErrorVariant::Named(this), ErrorVariant::SourceStruct(that)) => None,
+        (ErrorVariant::Named(this), ErrorVariant::SourceTuple(that)) => None,
+        (ErrorVariant::Struct(this), ErrorVariant::Named(that)) => None,
+        (ErrorVariant::Struct(this), ErrorVariant::Struct(that)) => Some(struct_to_struct(
+            from_error_enum_name,
+            &this.name,
+            &this.fields,
+            error_enum_name,
+            &that.name,
+            &that.fields,
+        )),
+        (ErrorVariant::Struct(this), ErrorVariant::SourceStruct(that)) => None,
+        (ErrorVariant::Struct(this), ErrorVariant::SourceTuple(that)) => None,
+        (ErrorVariant::SourceStruct(this), ErrorVariant::Named(that)) => None,
+        (ErrorVariant::SourceStruct(this), ErrorVariant::Struct(that)) => None,
+        (ErrorVariant::SourceStruct(this), ErrorVariant::SourceStruct(that)) => {
+            Some(source_struct_to_source_struct(
+                from_error_enum_name,
+                &this.name,
+                &this.fields,
+                error_enum_name,
+                &that.name,
+                &that.fields,
+            ))
+        }
+        (ErrorVariant::SourceStruct(this), ErrorVariant::SourceTuple(that)) => {
+            Some(source_struct_to_source_tuple(
+                from_error_enum_name,
+                &this.name,
+                &this.fields,
+                error_enum_name,
+                &that.name,
+            ))
+        }
+        (ErrorVariant::SourceTuple(this), ErrorVariant::Named(that)) => None,
+        (ErrorVariant::SourceTuple(this), ErrorVariant::Struct(that)) => None,
+        (ErrorVariant::SourceTuple(this), ErrorVariant::SourceStruct(that)) => {
+            if that.fields.iter().all(|e| e.default.is_some()) {
+                Some(source_tuple_to_source_struct(
+                    from_error_enum_name,
+                    &this.name,
+                    error_enum_name,
+                    &that.name,
+                    &that.fields,
+                ))
+            } else {
+                None
+            }
+        }
+        (ErrorVariant::SourceTuple(this), ErrorVariant::SourceTuple(that)) => {
+            Some(source_tuple_to_source_tuple(
+                from_error_enum_name,
+                &this.name,
+                error_enum_name,
+                &that.name,
+            ))
+        }
+    }
+}
+
 fn name_to_name(
     this_enum_name: &Ident,
     this_enum_variant_name: &Ident,
@@ -519,6 +1353,19 @@ fn name_to_name(
     }
 }
 
+fn name_to_struct(
+    this_enum_name: &Ident,
+    this_enum_variant_name: &Ident,
+    that_enum_name: &Ident,
+    that_enum_variant_name: &Ident,
+    that_enum_fields: &[AstInlineErrorVariantField],
+) -> TokenStream {
+    let that_field_inits = field_inits(&[], that_enum_fields);
+    quote::quote! {
+        #this_enum_name::#this_enum_variant_name =>  #that_enum_name::#that_enum_variant_name { #(#that_field_inits),*  },
+    }
+}
+
 fn struct_to_struct(
     this_enum_name: &Ident,
     this_variant_name: &Ident,
@@ -528,9 +1375,9 @@ fn struct_to_struct(
     that_enum_fields: &Vec<AstInlineErrorVariantField>,
 ) -> TokenStream {
     let this_field_names = this_enum_fields.iter().map(|e| &e.name);
-    let that_field_names = that_enum_fields.iter().map(|e| &e.name);
+    let that_field_inits = field_inits(this_enum_fields, that_enum_fields);
     quote::quote! {
-        #this_enum_name::#this_variant_name { #(#this_field_names),*  } =>  #that_enum_name::#that_variant_name { #(#that_field_names),*  },
+        #this_enum_name::#this_variant_name { #(#this_field_names),*  } =>  #that_enum_name::#that_variant_name { #(#that_field_inits),*  },
     }
 }
 
@@ -545,14 +1392,16 @@ fn source_tuple_to_source_tuple(
     }
 }
 
-fn source_tuple_to_source_only_struct(
+fn source_tuple_to_source_struct(
     this_enum_name: &Ident,
     this_enum_variant_name: &Ident,
     that_enum_name: &Ident,
     that_enum_variant_name: &Ident,
+    that_enum_fields: &[AstInlineErrorVariantField],
 ) -> TokenStream {
+    let that_field_inits = field_inits(&[], that_enum_fields);
     quote::quote! {
-        #this_enum_name::#this_enum_variant_name(source) =>  #that_enum_name::#that_enum_variant_name { source },
+        #this_enum_name::#this_enum_variant_name(source) =>  #that_enum_name::#that_enum_variant_name { source, #(#that_field_inits),* },
     }
 }
 
@@ -577,16 +1426,37 @@ fn source_struct_to_source_struct(
     that_enum_fields: &Vec<AstInlineErrorVariantField>,
 ) -> TokenStream {
     let this_field_names = this_enum_fields.iter().map(|e| &e.name);
-    let that_field_names = that_enum_fields.iter().map(|e| &e.name);
+    let that_field_inits = field_inits(this_enum_fields, that_enum_fields);
     quote::quote! {
-        #this_enum_name::#this_enum_variant_name { source, #(#this_field_names),*  } =>  #that_enum_name::#that_variant_name { source, #(#that_field_names),* },
+        #this_enum_name::#this_enum_variant_name { source, #(#this_field_names),*  } =>  #that_enum_name::#that_variant_name { source, #(#that_field_inits),* },
     }
 }
 
+/// The initializers for [that_enum_fields]. Fields missing from [this_enum_fields] are filled with their default.
+fn field_inits(
+    this_enum_fields: &[AstInlineErrorVariantField],
+    that_enum_fields: &[AstInlineErrorVariantField],
+) -> Vec<TokenStream> {
+    that_enum_fields
+        .iter()
+        .map(|that_field| {
+            let name = &that_field.name;
+            match &that_field.default {
+                Some(default) if !this_enum_fields.iter().any(|e| &e.name == name) => {
+                    quote::quote! { #name: #default }
+                }
+                _ => quote::quote! { #name },
+            }
+        })
+        .collect()
+}
+
 pub(crate) trait Common {
     fn attributes(&self) -> &Vec<Attribute>;
     fn cfg_attributes(&self) -> &Vec<Attribute>;
     fn display(&self) -> Option<&DisplayAttribute>;
+    fn disable_from(&self) -> bool;
+    fn io(&self) -> &IoAttributes;
     fn name(&self) -> &Ident;
     fn fields(&self) -> Option<&Vec<AstInlineErrorVariantField>>;
     fn source_type(&self) -> Option<&syn::TypePath>;
@@ -629,6 +1499,22 @@ impl Common for ErrorVariant {
             ErrorVariant::SourceTuple(e) => e.display(),
         }
     }
+    fn disable_from(&self) -> bool {
+        match self {
+            ErrorVariant::Named(e) => e.disable_from(),
+            ErrorVariant::Struct(e) => e.disable_from(),
+            ErrorVariant::SourceStruct(e) => e.disable_from(),
+            ErrorVariant::SourceTuple(e) => e.disable_from(),
+        }
+    }
+    fn io(&self) -> &IoAttributes {
+        match self {
+            ErrorVariant::Named(e) => e.io(),
+            ErrorVariant::Struct(e) => e.io(),
+            ErrorVariant::SourceStruct(e) => e.io(),
+            ErrorVariant::SourceTuple(e) => e.io(),
+        }
+    }
     fn name(&self) -> &Ident {
         match self {
             ErrorVariant::Named(e) => e.name(),
@@ -660,6 +1546,8 @@ pub(crate) struct Named {
     pub(crate) attributes: Vec<Attribute>,
     pub(crate) cfg_attributes: Vec<Attribute>,
     pub(crate) display: Option<DisplayAttribute>,
+    pub(crate) disable_from: bool,
+    pub(crate) io: IoAttributes,
     pub(crate) name: Ident,
 }
 
@@ -673,6 +1561,12 @@ impl Common for Named {
     fn display(&self) -> Option<&DisplayAttribute> {
         self.display.as_ref()
     }
+    fn disable_from(&self) -> bool {
+        self.disable_from
+    }
+    fn io(&self) -> &IoAttributes {
+        &self.io
+    }
     fn name(&self) -> &Ident {
         &self.name
     }
@@ -689,6 +1583,8 @@ pub(crate) struct Struct {
     pub(crate) attributes: Vec<Attribute>,
     pub(crate) cfg_attributes: Vec<Attribute>,
     pub(crate) display: Option<DisplayAttribute>,
+    pub(crate) disable_from: bool,
+    pub(crate) io: IoAttributes,
     pub(crate) name: Ident,
     // Dev Note: This field will never be empty. Otherwise it should just be a [Named]
     pub(crate) fields: Vec<AstInlineErrorVariantField>,
@@ -704,6 +1600,12 @@ impl Common for Struct {
     fn display(&self) -> Option<&DisplayAttribute> {
         self.display.as_ref()
     }
+    fn disable_from(&self) -> bool {
+        self.disable_from
+    }
+    fn io(&self) -> &IoAttributes {
+        &self.io
+    }
     fn name(&self) -> &Ident {
         &self.name
     }
@@ -720,6 +1622,8 @@ pub(crate) struct SourceStruct {
     pub(crate) attributes: Vec<Attribute>,
     pub(crate) cfg_attributes: Vec<Attribute>,
     pub(crate) display: Option<DisplayAttribute>,
+    pub(crate) disable_from: bool,
+    pub(crate) io: IoAttributes,
     pub(crate) name: Ident,
     pub(crate) source_type: syn::TypePath,
     // Dev Note: This field can be empty
@@ -736,6 +1640,12 @@ impl Common for SourceStruct {
     fn display(&self) -> Option<&DisplayAttribute> {
         self.display.as_ref()
     }
+    fn disable_from(&self) -> bool {
+        self.disable_from
+    }
+    fn io(&self) -> &IoAttributes {
+        &self.io
+    }
     fn name(&self) -> &Ident {
         &self.name
     }
@@ -752,6 +1662,10 @@ pub(crate) struct SourceTuple {
     pub(crate) attributes: Vec<Attribute>,
     pub(crate) cfg_attributes: Vec<Attribute>,
     pub(crate) display: Option<DisplayAttribute>,
+    pub(crate) disable_from: bool,
+    /// Created from `wrap(..)`
+    pub(crate) wrapped: bool,
+    pub(crate) io: IoAttributes,
     pub(crate) name: Ident,
     pub(crate) source_type: syn::TypePath,
 }
@@ -766,6 +1680,12 @@ impl Common for SourceTuple {
     fn display(&self) -> Option<&DisplayAttribute> {
         self.display.as_ref()
     }
+    fn disable_from(&self) -> bool {
+        self.disable_from
+    }
+    fn io(&self) -> &IoAttributes {
+        &self.io
+    }
     fn name(&self) -> &Ident {
         &self.name
     }
@@ -830,9 +1750,30 @@ pub(crate) struct ErrorEnum {
     pub(crate) error_name: Ident,
     pub(crate) generics: Vec<TypeParam>,
     pub(crate) disabled: Disabled,
+    pub(crate) converts: Vec<Convert>,
+    pub(crate) strict_match: bool,
+    /// Generate a `From` of the set for `std::io::Error`
+    pub(crate) into_io: bool,
+    pub(crate) vis: syn::Visibility,
+    pub(crate) derives: Vec<syn::Path>,
+    /// Generate a `Result` type alias
+    pub(crate) result_alias: bool,
+    pub(crate) non_exhaustive: bool,
+    pub(crate) display: Option<SetDisplay>,
+    /// Use the doc comment of a variant as the display message, if it has no `#[display(..)]`
+    pub(crate) doc_display: bool,
+    /// The variants declared in this set come first in [error_variants], before those of referenced sets.
+    pub(crate) own_variants_len: usize,
     pub(crate) error_variants: Vec<ErrorVariant>,
 }
 
+impl ErrorEnum {
+    /// The variants declared in this set, not copied from a referenced set.
+    pub(crate) fn own_variants(&self) -> &[ErrorVariant] {
+        &self.error_variants[..self.own_variants_len]
+    }
+}
+
 impl core::hash::Hash for ErrorEnum {
     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         self.error_name.hash(state);
@@ -849,48 +1790,72 @@ impl PartialEq for ErrorEnum {
 
 //************************************************************************//
 
-fn extract_string_if_str_literal(input: TokenStream) -> Option<String> {
-    if let Ok(expr) = syn::parse2::<Lit>(input) {
-        if let Lit::Str(lit) = expr {
-            return Some(lit.value());
+/// The first paragraph of the doc comments, with the lines joined by a space, and the span of the first doc comment.
+pub(crate) fn doc_message(attributes: &[Attribute]) -> Option<(String, proc_macro2::Span)> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut span = None;
+    'attributes: for attribute in attributes {
+        let syn::Meta::NameValue(name_value) = &attribute.meta else {
+            continue;
+        };
+        if !name_value.path.is_ident("doc") {
+            continue;
         }
+        let syn::Expr::Lit(syn::ExprLit {
+            lit: Lit::Str(doc), ..
+        }) = &name_value.value
+        else {
+            continue;
+        };
+        span.get_or_insert(doc.span());
+        for line in doc.value().split('\n') {
+            let line = line.trim();
+            if line.is_empty() {
+                if lines.is_empty() {
+                    continue;
+                }
+                break 'attributes;
+            }
+            lines.push(line.to_string());
+        }
+    }
+    if lines.is_empty() {
+        return None;
     }
-    None
+    Some((lines.join(" "), span.unwrap()))
 }
 
-// Dev Note: naive implementation.
-fn is_format_str(input: &str) -> bool {
-    let mut interpolation_candidate_found = false;
-    let mut last_char = 'a';
-
-    let mut start_count = 0;
-    let mut end_count = 0;
-
-    for c in input.chars() {
-        if c == '{' {
-            if last_char == '{' {
-                last_char = 'a';
-                start_count -= 1;
-                continue;
-            }
-            start_count += 1;
-        } else if c == '}' {
-            if last_char == '}' {
-                last_char = 'a';
-                end_count -= 1;
-                continue;
-            }
-            end_count += 1;
-            if start_count == end_count {
-                interpolation_candidate_found = true;
+/// `#[display(raw = "..")]`, printed without formatting
+pub(crate) fn raw_display(input: TokenStream) -> Option<syn::LitStr> {
+    syn::parse::Parser::parse2(
+        |input: syn::parse::ParseStream| {
+            let name = input.parse::<Ident>()?;
+            if name != "raw" {
+                return Err(syn::parse::Error::new(name.span(), "Expected `raw`"));
             }
-        }
-        last_char = c;
-    }
-    return interpolation_candidate_found && start_count == end_count;
+            input.parse::<syn::Token![=]>()?;
+            input.parse::<syn::LitStr>()
+        },
+        input,
+    )
+    .ok()
 }
 
-fn is_opaque(input: TokenStream) -> bool {
+/// Parses the [format_str] of a display, and rewrites it to only reference the fields of [variant] by name.
+/// e.g. `"{}"` for `IoError(std::io::Error)` becomes `"{source}"`.
+pub(crate) fn named_format_str(
+    format_str: &syn::LitStr,
+    variant: &ErrorVariant,
+) -> syn::Result<syn::LitStr> {
+    let pieces = format_str::parse(&format_str.value())
+        .map_err(|error| syn::parse::Error::new(format_str.span(), error))?;
+    let values = format_str::values(variant);
+    let named = format_str::to_named_format_str(&pieces, &values, &variant.name().to_string())
+        .map_err(|error| syn::parse::Error::new(format_str.span(), error))?;
+    Ok(syn::LitStr::new(&named, format_str.span()))
+}
+
+pub(crate) fn is_opaque(input: TokenStream) -> bool {
     if let Ok(ident) = syn::parse2::<Ident>(input) {
         ident == "opaque"
     } else {
@@ -918,6 +1883,19 @@ pub(crate) fn is_source_tuple_type(error_variant: &ErrorVariant) -> bool {
     return error_variant.source_type().is_some() && error_variant.fields().is_none();
 }
 
+/// e.g. `core::convert::Infallible` or `Infallible`
+fn is_infallible_type(type_path: &syn::TypePath) -> bool {
+    type_path
+        .path
+        .segments
+        .last()
+        .is_some_and(|e| e.ident == "Infallible" && e.arguments.is_none())
+}
+
+pub(crate) fn is_wrapped_type(error_variant: &ErrorVariant) -> bool {
+    matches!(error_variant, ErrorVariant::SourceTuple(source_tuple) if source_tuple.wrapped)
+}
+
 pub(crate) fn is_source_only_struct_type(error_variant: &ErrorVariant) -> bool {
     return error_variant.source_type().is_some()
         && error_variant
@@ -954,6 +1932,7 @@ pub(crate) fn is_source_struct_type(error_variant: &ErrorVariant) -> bool {
 /// }
 /// ```
 /// Thus, the names and shapes must be exactly the same to avoid this.
+/// Unless [strict] (`#[strict_match]`), field order is ignored and source types are compared by [is_same_source_type].
 /// Note, there can multiple source tuples or sources only structs with the same wrapped error types (different names).
 /// The first that is encountered becomes the `From` impl of that source error type.
 /// To ensure the correct one is selected, pay attention to `X = A || B` ordering
@@ -977,14 +1956,80 @@ pub(crate) fn is_source_struct_type(error_variant: &ErrorVariant) -> bool {
 ///     field: i32
 ///  }
 /// ```
-pub(crate) fn is_conversion_target(this: &ErrorVariant, that: &ErrorVariant) -> bool {
+pub(crate) fn is_conversion_target(this: &ErrorVariant, that: &ErrorVariant, strict: bool) -> bool {
     return match (&this.source_type(), &that.source_type()) {
         (Some(this_source_type), Some(other_source_type)) => {
-            this_source_type.path == other_source_type.path
+            is_same_source_type(this_source_type, other_source_type, strict)
                 && this.name() == that.name()
-                && this.fields() == that.fields()
+                && is_fields_conversion_target(this.fields(), that.fields(), strict)
+        }
+        (None, None) => {
+            this.name() == that.name()
+                && is_fields_conversion_target(this.fields(), that.fields(), strict)
         }
-        (None, None) => this.name() == that.name() && this.fields() == that.fields(),
         _ => false,
     };
 }
+
+/// Unless [strict], source types are the same when one path is a suffix of the other, including generic arguments,
+/// after a leading `std::`, `core::` or `alloc::` is stripped. e.g. `std::io::Error` == `io::Error` and
+/// `core::fmt::Error` == `std::fmt::Error`, but `fmt::Error` != `io::Error`.
+pub(crate) fn is_same_source_type(
+    this: &syn::TypePath,
+    that: &syn::TypePath,
+    strict: bool,
+) -> bool {
+    if strict || this.qself.is_some() || that.qself.is_some() {
+        return this == that;
+    }
+    let this = segments_without_std_prefix(&this.path);
+    let that = segments_without_std_prefix(&that.path);
+    let (shorter, longer) = if this.len() <= that.len() {
+        (this, that)
+    } else {
+        (that, this)
+    };
+    !shorter.is_empty() && longer.ends_with(&shorter)
+}
+
+fn segments_without_std_prefix(path: &syn::Path) -> Vec<&syn::PathSegment> {
+    let mut segments = path.segments.iter().collect::<Vec<_>>();
+    if segments.len() > 1
+        && ["std", "core", "alloc"]
+            .iter()
+            .any(|e| segments[0].ident == e)
+    {
+        segments.remove(0);
+    }
+    segments
+}
+
+/// The fields must be the same, except [that] may have additional fields with a default value.
+/// e.g. `A { field: String }` can be converted into `A { field: String, hint: Option<String> = None }`
+/// and `A` into `A { hint: Option<String> = None }`.
+/// Unless [strict], the order of the fields does not matter.
+fn is_fields_conversion_target(
+    this: Option<&Vec<AstInlineErrorVariantField>>,
+    that: Option<&Vec<AstInlineErrorVariantField>>,
+    strict: bool,
+) -> bool {
+    let (this, that) = match (this, that) {
+        (Some(this), Some(that)) => (&this[..], &that[..]),
+        (None, Some(that)) if !that.is_empty() && that.iter().all(|e| e.default.is_some()) => {
+            (&[][..], &that[..])
+        }
+        (this, that) => return this == that,
+    };
+    let mut this = this.iter().collect::<Vec<_>>();
+    let mut that_without_defaults = that
+        .iter()
+        .filter(|that_field| {
+            that_field.default.is_none() || this.iter().any(|e| e.name == that_field.name)
+        })
+        .collect::<Vec<_>>();
+    if !strict {
+        this.sort_by(|a, b| a.name.cmp(&b.name));
+        that_without_defaults.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+    this == that_without_defaults
+}
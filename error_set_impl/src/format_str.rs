@@ -0,0 +1,246 @@
+//! Parsing of the format strings in `#[display(..)]`, e.g. `"User `{name}` with role `{role:>4}` does not exist"`.
+
+use crate::expand::{Common, ErrorVariant};
+
+/// A reference to an argument of the format string.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum FormatArg {
+    /// `{}`
+    Next,
+    /// `{0}`
+    Index(usize),
+    /// `{name}`
+    Name(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum FormatPiece {
+    /// Text with `{{` and `}}` already unescaped
+    Literal(String),
+    Placeholder {
+        arg: FormatArg,
+        /// Everything after the `:`, with the `$` arguments of width and precision split out.
+        spec: Vec<SpecPiece>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum SpecPiece {
+    Text(String),
+    /// e.g. the `width` in `{:>width$}`
+    Arg(FormatArg),
+}
+
+/// Parses a format string, following the same rules as `format!`.
+pub(crate) fn parse(input: &str) -> Result<Vec<FormatPiece>, String> {
+    let mut pieces = Vec::new();
+    let mut literal = String::new();
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '}' => {
+                return Err(
+                    "Unmatched `}`. Use `}}` for a literal `}`, or `raw = \"..\"` to not format the string."
+                        .to_string(),
+                );
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some('{') | None => {
+                            return Err("Unmatched `{`. Use `{{` for a literal `{`, or `raw = \"..\"` to not format the string.".to_string());
+                        }
+                        Some(c) => placeholder.push(c),
+                    }
+                }
+                if !literal.is_empty() {
+                    pieces.push(FormatPiece::Literal(std::mem::take(&mut literal)));
+                }
+                pieces.push(parse_placeholder(&placeholder)?);
+            }
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        pieces.push(FormatPiece::Literal(literal));
+    }
+    Ok(pieces)
+}
+
+fn parse_placeholder(placeholder: &str) -> Result<FormatPiece, String> {
+    let (arg, spec) = match placeholder.split_once(':') {
+        Some((arg, spec)) => (arg, spec),
+        None => (placeholder, ""),
+    };
+    let arg = match parse_arg(arg.trim())? {
+        Some(arg) => arg,
+        None => FormatArg::Next,
+    };
+    Ok(FormatPiece::Placeholder {
+        arg,
+        spec: parse_spec(spec)?,
+    })
+}
+
+/// `None` if empty
+fn parse_arg(arg: &str) -> Result<Option<FormatArg>, String> {
+    if arg.is_empty() {
+        return Ok(None);
+    }
+    if arg.chars().all(|c| c.is_ascii_digit()) {
+        return arg
+            .parse()
+            .map(|e| Some(FormatArg::Index(e)))
+            .map_err(|_| format!("`{arg}` is not a valid argument index."));
+    }
+    let mut chars = arg.chars();
+    let is_ident = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_');
+    if !is_ident {
+        return Err(format!("`{arg}` is not a valid argument."));
+    }
+    Ok(Some(FormatArg::Name(arg.to_string())))
+}
+
+/// Splits out the `$` arguments, e.g. `>width$.prec$`. The rest of the spec is checked by `write!`.
+fn parse_spec(spec: &str) -> Result<Vec<SpecPiece>, String> {
+    if spec.contains(".*") {
+        return Err(
+            "`.*` is not supported, use a named precision like `{value:.prec$}` instead."
+                .to_string(),
+        );
+    }
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    for (index, part) in spec.split('$').enumerate() {
+        if index == spec.matches('$').count() {
+            text.push_str(part);
+            break;
+        }
+        // The argument is the trailing identifier or number before the `$`
+        let arg_start = part
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| c.is_alphanumeric() || *c == '_')
+            .last()
+            .map(|(i, _)| i);
+        // e.g. the `0` flag in `{:0width$}`
+        let arg_start = arg_start.map(|arg_start| {
+            let arg = &part[arg_start..];
+            if arg.len() > 1 && arg.starts_with('0') && !arg.chars().all(|c| c.is_ascii_digit()) {
+                arg_start + 1
+            } else {
+                arg_start
+            }
+        });
+        let Some(arg_start) = arg_start else {
+            // e.g. the fill in `{:$>4}`
+            text.push_str(part);
+            text.push('$');
+            continue;
+        };
+        text.push_str(&part[..arg_start]);
+        if !text.is_empty() {
+            pieces.push(SpecPiece::Text(std::mem::take(&mut text)));
+        }
+        let arg = parse_arg(&part[arg_start..])?.expect("not empty");
+        pieces.push(SpecPiece::Arg(arg));
+    }
+    if !text.is_empty() {
+        pieces.push(SpecPiece::Text(text));
+    }
+    Ok(pieces)
+}
+
+/// The values that can be referenced in the display of [variant]. Positional arguments refer to these in order.
+pub(crate) fn values(variant: &ErrorVariant) -> Vec<String> {
+    let mut values = Vec::new();
+    if variant.source_type().is_some() {
+        values.push("source".to_string());
+    }
+    if let Some(fields) = variant.fields() {
+        values.extend(fields.iter().map(|e| e.name.to_string()));
+    }
+    values
+}
+
+/// Rewrites [pieces] as a format string that only references [values] by name, so it can be used with the
+/// variables bound in the match arm of the variant. e.g. `"{} at {0:>1$}"` with values `source, width` becomes
+/// `"{source} at {source:>width$}"`.
+pub(crate) fn to_named_format_str(
+    pieces: &[FormatPiece],
+    values: &[String],
+    variant_name: &str,
+) -> Result<String, String> {
+    let mut next = 0;
+    let mut resolve = |arg: &FormatArg, is_next_allowed: bool| -> Result<String, String> {
+        let index = match arg {
+            FormatArg::Next if is_next_allowed => {
+                next += 1;
+                next - 1
+            }
+            FormatArg::Next => unreachable!("spec arguments are never empty"),
+            FormatArg::Index(index) => *index,
+            FormatArg::Name(name) => {
+                if values.contains(name) {
+                    return Ok(name.clone());
+                }
+                return Err(format!(
+                    "Unknown field `{name}` for '{variant_name}'. {}",
+                    expected_values(values)
+                ));
+            }
+        };
+        match values.get(index) {
+            Some(value) => Ok(value.clone()),
+            None => Err(format!(
+                "There is no argument {index} for '{variant_name}'. {}",
+                expected_values(values)
+            )),
+        }
+    };
+    let mut format_str = String::new();
+    for piece in pieces {
+        match piece {
+            FormatPiece::Literal(literal) => {
+                format_str.push_str(&literal.replace('{', "{{").replace('}', "}}"))
+            }
+            FormatPiece::Placeholder { arg, spec } => {
+                format_str.push('{');
+                format_str.push_str(&resolve(arg, true)?);
+                if !spec.is_empty() {
+                    format_str.push(':');
+                }
+                for spec_piece in spec {
+                    match spec_piece {
+                        SpecPiece::Text(text) => format_str.push_str(text),
+                        SpecPiece::Arg(arg) => {
+                            format_str.push_str(&resolve(arg, false)?);
+                            format_str.push('$');
+                        }
+                    }
+                }
+                format_str.push('}');
+            }
+        }
+    }
+    Ok(format_str)
+}
+
+fn expected_values(values: &[String]) -> String {
+    if values.is_empty() {
+        "The variant has no fields.".to_string()
+    } else {
+        format!("Expected one of: {}.", values.join(", "))
+    }
+}
@@ -1,5 +1,6 @@
 mod ast;
 mod expand;
+mod format_str;
 mod resolve;
 mod validate;
 
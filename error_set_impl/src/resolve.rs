@@ -1,10 +1,12 @@
 use std::collections::HashMap;
 
 use crate::ast::{
-    AstErrorDeclaration, AstErrorSet, AstErrorVariant, AstInlineErrorVariantField, Disabled,
-    RefError,
+    AstErrorDeclaration, AstErrorSet, AstErrorVariant, AstInlineErrorVariantField, Convert,
+    Disabled, RefError, SetDisplay, SetOptions,
+};
+use crate::expand::{
+    is_same_source_type, ErrorEnum, ErrorVariant, Named, SourceStruct, SourceTuple, Struct,
 };
-use crate::expand::{ErrorEnum, ErrorVariant, Named, SourceStruct, SourceTuple, Struct};
 
 use quote::ToTokens;
 use syn::{Attribute, Ident, TypeParam};
@@ -20,11 +22,29 @@ pub(crate) fn resolve(error_set: AstErrorSet) -> syn::Result<Vec<ErrorEnum>> {
             error_name,
             generics,
             disabled,
+            converts,
+            strict_match,
+            into_io,
+            options,
+            display,
             parts,
         } = declaration;
+        // A `#[disable(..)]` on the set overrides `disable(..)` from the options
+        let disabled = disabled
+            .or_else(|| options.disabled.clone())
+            .unwrap_or_default();
 
-        let mut error_enum_builder =
-            ErrorEnumBuilder::new(error_name, attributes, generics, disabled);
+        let mut error_enum_builder = ErrorEnumBuilder::new(
+            error_name,
+            attributes,
+            generics,
+            disabled,
+            converts,
+            strict_match,
+            into_io,
+            options,
+            display,
+        );
 
         for part in parts.into_iter() {
             match part {
@@ -36,8 +56,12 @@ pub(crate) fn resolve(error_set: AstErrorSet) -> syn::Result<Vec<ErrorEnum>> {
                 crate::ast::AstInlineOrRefError::Ref(ref_part) => {
                     error_enum_builder.add_ref_part(ref_part);
                 }
+                crate::ast::AstInlineOrRefError::Wrap(wrap_part) => {
+                    error_enum_builder.add_wrap_part(wrap_part);
+                }
             }
         }
+        error_enum_builder.own_variants_len = error_enum_builder.error_variants.len();
         error_enum_builders.push(error_enum_builder);
     }
     let error_enums = resolve_builders(error_enum_builders)?;
@@ -46,6 +70,25 @@ pub(crate) fn resolve(error_set: AstErrorSet) -> syn::Result<Vec<ErrorEnum>> {
 }
 
 fn resolve_builders(mut error_enum_builders: Vec<ErrorEnumBuilder>) -> syn::Result<Vec<ErrorEnum>> {
+    for error_enum_builder in error_enum_builders.iter() {
+        for wrap_part in error_enum_builder.wrap_parts.iter() {
+            let Some(wrapped_error_enum_builder) = error_enum_builders
+                .iter()
+                .find(|e| e.error_name == wrap_part.name)
+            else {
+                return Err(syn::parse::Error::new_spanned(
+                    &wrap_part.name,
+                    "Not a declared error set.",
+                ));
+            };
+            if wrap_part.generic_refs.len() != wrapped_error_enum_builder.generics.len() {
+                return Err(syn::parse::Error::new_spanned(
+                    &wrap_part.name,
+                    format!("A reference to {} was declared with {} generic param(s), but the original definition takes {}.", wrap_part.name, wrap_part.generic_refs.len(), wrapped_error_enum_builder.generics.len()),
+                ));
+            }
+        }
+    }
     for index in 0..error_enum_builders.len() {
         if !error_enum_builders[index].ref_parts_to_resolve.is_empty() {
             resolve_builders_helper(index, &mut *error_enum_builders, &mut Vec::new())?;
@@ -86,6 +129,10 @@ fn resolve_builders_helper<'a>(
     let ref_parts_to_resolve = error_enum_builder.ref_parts_to_resolve.clone();
     // If this enums ref parts have not been resolved, resolve them.
     if !ref_parts_to_resolve.is_empty() {
+        // Variants declared directly in this set override referenced variants of the same name
+        let own_variants_len = error_enum_builder.error_variants.len();
+        // The referenced set each variant after [own_variants_len] came from
+        let mut ref_variant_origins: Vec<Ident> = Vec::new();
         for ref_part in ref_parts_to_resolve {
             let ref_error_enum_index = error_enum_builders
                 .iter()
@@ -181,6 +228,9 @@ fn resolve_builders_helper<'a>(
                         attributes: error_variant.attributes.clone(),
                         cfg_attributes: error_variant.cfg_attributes.clone(),
                         display: error_variant.display.clone(),
+                        disable_from: error_variant.disable_from,
+                        wrapped: error_variant.wrapped,
+                        io: error_variant.io.clone(),
                         name: error_variant.name.clone(),
                         fields: new_fields,
                         source_type: error_variant.source_type.clone(),
@@ -189,13 +239,34 @@ fn resolve_builders_helper<'a>(
                 }
                 &error_variants
             };
+            let strict = this_error_enum_builder.strict_match;
             for variant in error_variants {
                 let this_error_variants = &mut this_error_enum_builder.error_variants;
-                let is_variant_already_in_enum = this_error_variants
+                match this_error_variants
                     .iter()
-                    .any(|e| does_occupy_the_same_space(e, &variant));
-                if !is_variant_already_in_enum {
-                    this_error_variants.push(variant.clone());
+                    .position(|e| does_occupy_the_same_space(e, variant))
+                {
+                    None => {
+                        // A variant level `#[disable(From)]` only applies to the set it was declared in
+                        this_error_variants.push(AstErrorVariant {
+                            disable_from: false,
+                            ..variant.clone()
+                        });
+                        ref_variant_origins.push(ref_part.name.clone());
+                    }
+                    Some(existing_index) if existing_index >= own_variants_len => {
+                        let existing_variant = &this_error_variants[existing_index];
+                        if !is_same_shape(existing_variant, variant, strict) {
+                            return Err(conflicting_variants_error(
+                                &this_error_enum_builder.error_name,
+                                existing_variant,
+                                &ref_variant_origins[existing_index - own_variants_len],
+                                variant,
+                                &ref_part.name,
+                            ));
+                        }
+                    }
+                    Some(_) => {}
                 }
             }
         }
@@ -211,6 +282,51 @@ pub(crate) fn does_occupy_the_same_space(this: &AstErrorVariant, other: &AstErro
     return this.name == other.name;
 }
 
+/// If the variants have the same fields and source type. Unless [strict], field order is ignored and source types
+/// are compared by [is_same_source_type], so `fmt::Error` and `io::Error` are different shapes.
+fn is_same_shape(this: &AstErrorVariant, other: &AstErrorVariant, strict: bool) -> bool {
+    let is_same_source_type = match (&this.source_type, &other.source_type) {
+        (Some(this), Some(other)) => is_same_source_type(this, other, strict),
+        (None, None) => true,
+        _ => false,
+    };
+    let is_same_fields = match (&this.fields, &other.fields) {
+        (Some(this), Some(other)) if !strict => {
+            let mut this = this.iter().collect::<Vec<_>>();
+            let mut other = other.iter().collect::<Vec<_>>();
+            this.sort_by(|a, b| a.name.cmp(&b.name));
+            other.sort_by(|a, b| a.name.cmp(&b.name));
+            this == other
+        }
+        (this, other) => this == other,
+    };
+    is_same_source_type && is_same_fields
+}
+
+/// Points at both declarations of a variant that is declared with different shapes in two referenced sets.
+fn conflicting_variants_error(
+    error_name: &Ident,
+    this: &AstErrorVariant,
+    this_origin: &Ident,
+    other: &AstErrorVariant,
+    other_origin: &Ident,
+) -> syn::Error {
+    let name = &other.name;
+    let mut error = syn::parse::Error::new_spanned(
+        name,
+        format!(
+            "'{name}' in '{other_origin}' conflicts with '{name}' in '{this_origin}', which has a different shape. \
+            Rename one of them, or declare '{name}' directly in '{error_name}' to override both, \
+            e.g. `{error_name} = {{ {name}(..) }} || {this_origin} || {other_origin}`."
+        ),
+    );
+    error.combine(syn::parse::Error::new_spanned(
+        &this.name,
+        format!("'{name}' is first declared here in '{this_origin}'."),
+    ));
+    error
+}
+
 // fn merge_generics(this: &mut Generics, other: &Generics) {
 //     let other_params = other.params.iter().collect::<Vec<_>>();
 //     for other_param in other_params {
@@ -233,31 +349,77 @@ struct ErrorEnumBuilder {
     pub error_name: Ident,
     pub generics: Vec<TypeParam>,
     pub disabled: Disabled,
+    pub converts: Vec<Convert>,
+    pub strict_match: bool,
+    pub into_io: bool,
+    pub options: SetOptions,
+    pub display: Option<SetDisplay>,
     pub error_variants: Vec<AstErrorVariant>,
+    /// The variants declared in this set come first in [error_variants], before those of referenced sets.
+    pub own_variants_len: usize,
     /// Once this is empty, all [ref_parts] have been resolved and [error_variants] is complete.
     pub ref_parts_to_resolve: Vec<RefError>,
+    /// Sets nested with `wrap(..)`. Their variants are already in [error_variants].
+    pub wrap_parts: Vec<RefError>,
 }
 
 impl ErrorEnumBuilder {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         error_name: Ident,
         attributes: Vec<Attribute>,
         generics: Vec<TypeParam>,
         disabled: Disabled,
+        converts: Vec<Convert>,
+        strict_match: bool,
+        into_io: bool,
+        options: SetOptions,
+        display: Option<SetDisplay>,
     ) -> Self {
         Self {
             attributes,
             error_name,
             generics,
             disabled,
+            converts,
+            strict_match,
+            into_io,
+            options,
+            display,
             error_variants: Vec::new(),
+            own_variants_len: 0,
             ref_parts_to_resolve: Vec::new(),
+            wrap_parts: Vec::new(),
         }
     }
 
     fn add_ref_part(&mut self, ref_part: RefError) {
         self.ref_parts_to_resolve.push(ref_part);
     }
+
+    /// `wrap(X<T>)` becomes the variant `X(X<T>)`
+    fn add_wrap_part(&mut self, wrap_part: RefError) {
+        let name = &wrap_part.name;
+        let source_type: syn::TypePath = if wrap_part.generic_refs.is_empty() {
+            syn::parse_quote! { #name }
+        } else {
+            let generic_refs = &wrap_part.generic_refs;
+            syn::parse_quote! { #name<#(#generic_refs),*> }
+        };
+        self.error_variants.push(AstErrorVariant {
+            attributes: Vec::new(),
+            cfg_attributes: Vec::new(),
+            display: None,
+            disable_from: false,
+            wrapped: true,
+            io: Default::default(),
+            name: name.clone(),
+            fields: None,
+            source_type: Some(source_type),
+            backtrace_type: None,
+        });
+        self.wrap_parts.push(wrap_part);
+    }
 }
 
 impl From<ErrorEnumBuilder> for ErrorEnum {
@@ -271,6 +433,16 @@ impl From<ErrorEnumBuilder> for ErrorEnum {
             error_name: value.error_name,
             generics: value.generics,
             disabled: value.disabled,
+            converts: value.converts,
+            strict_match: value.strict_match,
+            into_io: value.into_io,
+            vis: value.options.vis.unwrap_or_else(|| syn::parse_quote!(pub)),
+            derives: value.options.derives.unwrap_or_default(),
+            result_alias: value.options.result_alias.unwrap_or(false),
+            non_exhaustive: value.options.non_exhaustive.unwrap_or(false),
+            doc_display: value.options.doc_display.unwrap_or(false),
+            display: value.display,
+            own_variants_len: value.own_variants_len,
             error_variants: value
                 .error_variants
                 .into_iter()
@@ -301,6 +473,9 @@ fn reshape(this: AstErrorVariant) -> ErrorVariant {
         attributes,
         cfg_attributes,
         display,
+        disable_from,
+        wrapped,
+        io,
         name,
         fields,
         source_type,
@@ -313,6 +488,8 @@ fn reshape(this: AstErrorVariant) -> ErrorVariant {
                 attributes,
                 cfg_attributes,
                 display,
+                disable_from,
+                io,
                 name,
                 source_type,
                 fields,
@@ -324,6 +501,8 @@ fn reshape(this: AstErrorVariant) -> ErrorVariant {
                 attributes,
                 cfg_attributes,
                 display,
+                disable_from,
+                io,
                 name,
                 fields,
             });
@@ -334,6 +513,9 @@ fn reshape(this: AstErrorVariant) -> ErrorVariant {
                 attributes,
                 cfg_attributes,
                 display,
+                disable_from,
+                wrapped,
+                io,
                 name,
                 source_type,
             });
@@ -344,6 +526,8 @@ fn reshape(this: AstErrorVariant) -> ErrorVariant {
                 attributes,
                 cfg_attributes,
                 display,
+                disable_from,
+                io,
                 name,
             });
         }
@@ -363,6 +547,7 @@ fn replace_generics_in_fields(
         return AstInlineErrorVariantField {
             name: field.name.clone(),
             r#type: new_type.clone(),
+            ..field.clone()
         };
     }
     // return field.clone();
@@ -376,6 +561,7 @@ fn replace_generics_in_fields(
             return AstInlineErrorVariantField {
                 name: field.name.clone(),
                 r#type: new_type.clone(),
+                ..field.clone()
             };
         }
     }
@@ -2,12 +2,99 @@ use std::collections::HashSet;
 
 use syn::Ident;
 
-use crate::expand::{Common, ErrorEnum};
+use crate::ast::SetDisplay;
+use crate::expand::{
+    constructor_name, context_selector_name, doc_message, is_conversion_target, is_opaque,
+    is_same_source_type, is_source_only_struct_type, is_source_tuple_type, named_format_str,
+    raw_display, Common, ErrorEnum, ErrorVariant,
+};
+use crate::format_str::{FormatArg, FormatPiece, SpecPiece};
 
 /// Additional validation logic
 pub fn validate(error_enums: &Vec<ErrorEnum>) -> Result<(), syn::Error> {
     all_enums_have_unique_names(error_enums)?;
-    unique_variant_names_per_enum(error_enums)
+    unique_variant_names_per_enum(error_enums)?;
+    unique_constructor_names_per_enum(error_enums)?;
+    context_selectors_do_not_shadow_enums(error_enums)?;
+    context_selectors_have_the_same_fields(error_enums)?;
+    from_io_variants_have_a_fallback(error_enums)?;
+    io_kinds_have_into_io(error_enums)?;
+    empty_enums_are_not_generic(error_enums)?;
+    display_format_strs_are_valid(error_enums)?;
+    converts_are_complete(error_enums)
+}
+
+/// The format strings of `#[display(..)]`, and doc comments with `doc_display`, only reference fields of the variant.
+fn display_format_strs_are_valid(error_enums: &Vec<ErrorEnum>) -> Result<(), syn::Error> {
+    for error_enum in error_enums {
+        if error_enum.disabled.display {
+            continue;
+        }
+        if let Some(SetDisplay::Template(template)) = &error_enum.display {
+            set_display_template_is_valid(template)?;
+        }
+        for variant in &error_enum.error_variants {
+            if let Some(display) = variant.display() {
+                let tokens = &display.tokens;
+                if is_opaque(tokens.clone()) || raw_display(tokens.clone()).is_some() {
+                    continue;
+                }
+                if let Ok(format_str) = syn::parse2::<syn::LitStr>(tokens.clone()) {
+                    named_format_str(&format_str, variant)?;
+                }
+            } else if error_enum.doc_display {
+                let Some((doc, span)) = doc_message(variant.attributes()) else {
+                    continue;
+                };
+                // Not a format string, so the doc comment is printed as is
+                if crate::format_str::parse(&doc).is_err() {
+                    continue;
+                }
+                named_format_str(&syn::LitStr::new(&doc, span), variant)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The template of a set's `#[display(..)]` has exactly one `{}`, where the message of the variant is written.
+fn set_display_template_is_valid(template: &syn::LitStr) -> Result<(), syn::Error> {
+    let pieces = crate::format_str::parse(&template.value())
+        .map_err(|error| syn::parse::Error::new(template.span(), error))?;
+    let placeholders: Vec<&FormatPiece> = pieces
+        .iter()
+        .filter(|piece| matches!(piece, FormatPiece::Placeholder { .. }))
+        .collect();
+    let is_message = matches!(
+        placeholders.as_slice(),
+        [FormatPiece::Placeholder {
+            arg: FormatArg::Next | FormatArg::Index(0),
+            spec,
+        }] if spec.iter().all(|piece| matches!(piece, SpecPiece::Text(_)))
+    );
+    if !is_message {
+        return Err(syn::parse::Error::new(
+            template.span(),
+            "The `display` template of a set must contain exactly one `{}`, which is replaced by the message of the variant.",
+        ));
+    }
+    Ok(())
+}
+
+/// An empty set is uninhabited, so generic params would go unused.
+fn empty_enums_are_not_generic(error_enums: &Vec<ErrorEnum>) -> Result<(), syn::Error> {
+    for error_enum in error_enums {
+        if error_enum.error_variants.is_empty() && !error_enum.generics.is_empty() {
+            return Err(syn::parse::Error::new_spanned(
+                &error_enum.error_name,
+                format!(
+                    "'{0}' is empty and cannot be generic.",
+                    error_enum.error_name
+                ),
+            ));
+        }
+    }
+    Ok(())
 }
 
 fn all_enums_have_unique_names(error_enums: &Vec<ErrorEnum>) -> Result<(), syn::Error> {
@@ -49,3 +136,340 @@ fn unique_variant_names_per_enum(
     }
     Ok(())
 }
+
+/// e.g. `IOError` and `IoError` would both have the constructor `io_error`.
+fn unique_constructor_names_per_enum(error_enums: &Vec<ErrorEnum>) -> Result<(), syn::Error> {
+    for error_enum in error_enums {
+        if error_enum.disabled.constructors {
+            continue;
+        }
+        let mut constructors: Vec<(Ident, &Ident)> = Vec::new();
+        for variant in &error_enum.error_variants {
+            let (ErrorVariant::Struct(_) | ErrorVariant::SourceStruct(_)) = variant else {
+                continue;
+            };
+            let constructor_name = constructor_name(variant);
+            if let Some((_, other)) = constructors.iter().find(|(e, _)| *e == constructor_name) {
+                return Err(syn::parse::Error::new(
+                    variant.name().span(),
+                    format!(
+                        "The constructor `{constructor_name}` of '{0}' conflicts with the one of '{other}' in error enum '{1}'. \
+                        Rename one of the variants or add `#[disable(Constructors)]` to the set.",
+                        variant.name(),
+                        error_enum.error_name
+                    ),
+                ));
+            }
+            constructors.push((constructor_name, variant.name()));
+        }
+    }
+    Ok(())
+}
+
+/// Context selectors are generated next to the sets, so they must not share a name with a set.
+fn context_selectors_do_not_shadow_enums(error_enums: &Vec<ErrorEnum>) -> Result<(), syn::Error> {
+    for error_enum in error_enums {
+        if error_enum.disabled.context_selectors {
+            continue;
+        }
+        for variant in error_enum.own_variants() {
+            let ErrorVariant::SourceStruct(_) = variant else {
+                continue;
+            };
+            let selector_name = context_selector_name(variant);
+            if error_enums.iter().any(|e| e.error_name == selector_name) {
+                return Err(syn::parse::Error::new(
+                    variant.name().span(),
+                    format!(
+                        "The context selector `{selector_name}` of '{0}::{1}' conflicts with the error enum of the same name.",
+                        error_enum.error_name,
+                        variant.name(),
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Sets that declare their own source struct variant of the same name share its context selector, so the variants must
+/// have the same fields without a default value.
+fn context_selectors_have_the_same_fields(error_enums: &[ErrorEnum]) -> Result<(), syn::Error> {
+    let selector_field_names = |variant: &ErrorVariant| {
+        let mut names = variant
+            .fields()
+            .into_iter()
+            .flatten()
+            .filter(|e| e.default.is_none())
+            .map(|e| e.name.to_string())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    };
+    for (index, error_enum) in error_enums.iter().enumerate() {
+        if error_enum.disabled.context_selectors {
+            continue;
+        }
+        for variant in error_enum.own_variants() {
+            let ErrorVariant::SourceStruct(_) = variant else {
+                continue;
+            };
+            let selector_name = context_selector_name(variant);
+            let first = error_enums[..index]
+                .iter()
+                .filter(|e| !e.disabled.context_selectors)
+                .find_map(|other_error_enum| {
+                    other_error_enum
+                        .own_variants()
+                        .iter()
+                        .find(|e| {
+                            matches!(e, ErrorVariant::SourceStruct(_))
+                                && context_selector_name(e) == selector_name
+                        })
+                        .map(|e| (other_error_enum, e))
+                });
+            let Some((first_error_enum, first_variant)) = first else {
+                continue;
+            };
+            if selector_field_names(first_variant) != selector_field_names(variant) {
+                let mut error = syn::parse::Error::new(
+                    variant.name().span(),
+                    format!(
+                        "'{0}::{1}' and '{2}::{1}' share the context selector `{selector_name}`, so they must have the \
+                        same fields without a default value. Rename one of the variants.",
+                        error_enum.error_name,
+                        variant.name(),
+                        first_error_enum.error_name,
+                    ),
+                );
+                error.combine(syn::parse::Error::new(
+                    first_variant.name().span(),
+                    format!(
+                        "'{0}' is first declared here in '{1}'.",
+                        variant.name(),
+                        first_error_enum.error_name
+                    ),
+                ));
+                return Err(error);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `#[io_kind(..)]` sets the kind of the `std::io::Error` created by `#[into_io]`, so it has no effect without it.
+fn io_kinds_have_into_io(error_enums: &[ErrorEnum]) -> Result<(), syn::Error> {
+    for error_enum in error_enums {
+        if error_enum.into_io {
+            continue;
+        }
+        for variant in error_enum.own_variants() {
+            if let Some(kind) = &variant.io().kind {
+                return Err(syn::parse::Error::new_spanned(
+                    kind,
+                    format!(
+                        "'{0}::{1}' has `#[io_kind(..)]`, but '{0}' does not have `#[into_io]`.",
+                        error_enum.error_name,
+                        variant.name()
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `#[from_io(..)]` variants are created by the `From` of the variant with the same source and without `#[from_io]`,
+/// which receives all other kinds.
+fn from_io_variants_have_a_fallback(error_enums: &Vec<ErrorEnum>) -> Result<(), syn::Error> {
+    for error_enum in error_enums {
+        for variant in &error_enum.error_variants {
+            if variant.io().from_kinds.is_empty() {
+                continue;
+            }
+            if !is_source_tuple_type(variant) && !is_source_only_struct_type(variant) {
+                return Err(syn::parse::Error::new(
+                    variant.name().span(),
+                    format!(
+                        "'{0}' has `#[from_io(..)]`, so it must only hold the source, e.g. `{0}(std::io::Error)`.",
+                        variant.name()
+                    ),
+                ));
+            }
+            let source_type = variant.source_type().expect("checked to hold a source");
+            if !is_same_source_type(source_type, &syn::parse_quote!(std::io::Error), false) {
+                return Err(syn::parse::Error::new_spanned(
+                    source_type,
+                    format!(
+                        "'{0}' has `#[from_io(..)]`, so its source must be `std::io::Error`.",
+                        variant.name()
+                    ),
+                ));
+            }
+            let has_fallback = error_enum.error_variants.iter().any(|e| {
+                e.io().from_kinds.is_empty()
+                    && !e.disable_from()
+                    && e.source_type().is_some_and(|e| {
+                        is_same_source_type(e, source_type, error_enum.strict_match)
+                    })
+            });
+            if !has_fallback {
+                return Err(syn::parse::Error::new(
+                    variant.name().span(),
+                    format!(
+                        "'{0}' has `#[from_io(..)]`, but '{1}' has no variant with the same source and without it, \
+                        or `#[disable(From)]`, which would receive the other kinds.",
+                        variant.name(),
+                        error_enum.error_name
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// If the converted set can be wrapped by a fallback with this source with `.into()`. That is, the source is the set
+/// itself, a set declared alongside it that converts from it, or a boxed trait object, e.g. `Box<dyn Error>`.
+fn is_fallback_source(
+    source_type: &syn::TypePath,
+    from_error_enum: &ErrorEnum,
+    error_enums: &[ErrorEnum],
+) -> bool {
+    let Some(source_segment) = source_type.path.segments.last() else {
+        return false;
+    };
+    let source_name = &source_segment.ident;
+    if source_name == "Box" {
+        return matches!(
+            &source_segment.arguments,
+            syn::PathArguments::AngleBracketed(arguments)
+                if matches!(
+                    arguments.args.first(),
+                    Some(syn::GenericArgument::Type(syn::Type::TraitObject(_)))
+                )
+        );
+    }
+    if source_name == &from_error_enum.error_name {
+        return true;
+    }
+    let Some(source_error_enum) = error_enums.iter().find(|e| &e.error_name == source_name) else {
+        return false;
+    };
+    let strict = source_error_enum.strict_match || from_error_enum.strict_match;
+    source_error_enum
+        .converts
+        .iter()
+        .any(|e| e.from == from_error_enum.error_name)
+        || from_error_enum.error_variants.iter().all(|from_variant| {
+            source_error_enum
+                .error_variants
+                .iter()
+                .any(|e| !e.disable_from() && is_conversion_target(from_variant, e, strict))
+        })
+}
+
+/// Every variant of a `#[convert(from = ..)]` set must be mapped, automatically convertible, or absorbed by the fallback.
+fn converts_are_complete(error_enums: &Vec<ErrorEnum>) -> Result<(), syn::Error> {
+    for error_enum in error_enums {
+        for convert in &error_enum.converts {
+            let Some(from_error_enum) = error_enums.iter().find(|e| e.error_name == convert.from)
+            else {
+                return Err(syn::parse::Error::new_spanned(
+                    &convert.from,
+                    "Not a declared error set.",
+                ));
+            };
+            if from_error_enum.error_name == error_enum.error_name {
+                return Err(syn::parse::Error::new_spanned(
+                    &convert.from,
+                    "An error set cannot convert from itself.",
+                ));
+            }
+            if !from_error_enum.generics.is_empty()
+                && error_enum.generics != from_error_enum.generics
+            {
+                return Err(syn::parse::Error::new_spanned(
+                    &convert.from,
+                    format!(
+                        "'{0}' must declare the same generics as '{1}' to convert from it.",
+                        error_enum.error_name, from_error_enum.error_name
+                    ),
+                ));
+            }
+            for map in &convert.maps {
+                if !from_error_enum
+                    .error_variants
+                    .iter()
+                    .any(|e| e.name() == &map.from_variant)
+                {
+                    return Err(syn::parse::Error::new_spanned(
+                        &map.from_variant,
+                        format!(
+                            "'{0}' is not a variant of '{1}'",
+                            map.from_variant, from_error_enum.error_name
+                        ),
+                    ));
+                }
+            }
+            if let Some(fallback) = &convert.fallback {
+                let Some(fallback_variant) = error_enum
+                    .error_variants
+                    .iter()
+                    .find(|e| e.name() == fallback)
+                else {
+                    return Err(syn::parse::Error::new_spanned(
+                        fallback,
+                        format!(
+                            "'{0}' is not a variant of '{1}'",
+                            fallback, error_enum.error_name
+                        ),
+                    ));
+                };
+                if fallback_variant.fields().is_some_and(|e| !e.is_empty())
+                    && !is_source_only_struct_type(fallback_variant)
+                {
+                    return Err(syn::parse::Error::new_spanned(
+                        fallback,
+                        "A fallback variant must be a unit variant or only wrap a source error.",
+                    ));
+                }
+                if let Some(source_type) = fallback_variant.source_type() {
+                    if !is_fallback_source(source_type, from_error_enum, error_enums) {
+                        return Err(syn::parse::Error::new_spanned(
+                            source_type,
+                            format!(
+                                "The fallback '{0}' must wrap '{1}', or a type '{1}' converts into, e.g. `{0}({1})` or `{0}(Box<dyn Error>)`.",
+                                fallback, from_error_enum.error_name
+                            ),
+                        ));
+                    }
+                }
+                continue;
+            }
+            let strict = error_enum.strict_match || from_error_enum.strict_match;
+            for from_variant in &from_error_enum.error_variants {
+                let is_mapped = convert
+                    .maps
+                    .iter()
+                    .any(|e| &e.from_variant == from_variant.name());
+                let is_converted = error_enum
+                    .error_variants
+                    .iter()
+                    .any(|e| !e.disable_from() && is_conversion_target(from_variant, e, strict));
+                if !is_mapped && !is_converted {
+                    return Err(syn::parse::Error::new_spanned(
+                        &convert.from,
+                        format!(
+                            "'{0}::{1}' cannot be converted into '{2}'. Add it to `map(..)` or provide a `fallback`.",
+                            from_error_enum.error_name,
+                            from_variant.name(),
+                            error_enum.error_name
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}